@@ -1,6 +1,6 @@
 use node_template_runtime::{
-    AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-    SystemConfig, WASM_BINARY
+    AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, KittiesConfig, Signature,
+    SudoConfig, SystemConfig, WASM_BINARY
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -38,6 +38,15 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
     (get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// Demo kitties minted at genesis so front ends have data after every chain reset.
+pub fn demo_kitties() -> Vec<(AccountId, Option<[u8; 16]>)> {
+    vec![
+        (get_account_id_from_seed::<sr25519::Public>("Alice"), None),
+        (get_account_id_from_seed::<sr25519::Public>("Alice"), None),
+        (get_account_id_from_seed::<sr25519::Public>("Bob"), None),
+    ]
+}
+
 pub fn x2x4_uat_testnet_config() -> Result<ChainSpec, String> {
     let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -61,6 +70,8 @@ pub fn x2x4_uat_testnet_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
                 ],
+                // Genesis kitties
+                demo_kitties(),
                 true,
             )
         },
@@ -103,6 +114,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
                 ],
+                // Genesis kitties
+                demo_kitties(),
                 true,
             )
         },
@@ -153,6 +166,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                     get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
                     get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
                 ],
+                // Genesis kitties
+                demo_kitties(),
                 true,
             )
         },
@@ -175,6 +190,7 @@ fn testnet_genesis(
     initial_authorities: Vec<(AuraId, GrandpaId)>,
     root_key: AccountId,
     endowed_accounts: Vec<AccountId>,
+    initial_kitties: Vec<(AccountId, Option<[u8; 16]>)>,
     _enable_println: bool,
 ) -> GenesisConfig {
     GenesisConfig {
@@ -204,5 +220,9 @@ fn testnet_genesis(
             // Assign network admin rights.
             key: root_key,
         },
+        kitties: KittiesConfig {
+            // Kitty owners must be endowed to pay the kitty reserve.
+            kitties: initial_kitties,
+        },
     }
 }
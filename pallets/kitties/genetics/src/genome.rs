@@ -0,0 +1,138 @@
+//! Mendelian inheritance of kitty genes.
+//!
+//! Each trait carries four genes: the dominant gene, which is expressed and decoded by
+//! [`Traits`](crate::Traits), followed by three hidden recessive genes. When breeding,
+//! each parent's recessive genes may move up, so they can surface in the child, and each
+//! child gene is then taken from either parent. Some gene pairs combine into a rarer gene.
+//! Given the same parents and seed the child is always the same.
+
+use crate::{slot_of, BODY_BYTE, EYE_COLOUR_BYTE, GENE_TABLE, PATTERN_BYTE};
+
+/// Number of genes per trait: one dominant followed by three recessive.
+pub const GENES_PER_TRAIT: usize = 4;
+
+/// DNA bytes of the body genes, dominant first.
+pub const BODY_GENES: [usize; GENES_PER_TRAIT] = [BODY_BYTE, 3, 4, 5];
+/// DNA bytes of the pattern genes, dominant first.
+pub const PATTERN_GENES: [usize; GENES_PER_TRAIT] = [PATTERN_BYTE, 6, 7, 8];
+/// DNA bytes of the eye colour genes, dominant first.
+pub const EYE_COLOUR_GENES: [usize; GENES_PER_TRAIT] = [EYE_COLOUR_BYTE, 9, 10, 11];
+/// Genes of every trait.
+pub const TRAIT_GENES: [[usize; GENES_PER_TRAIT]; 3] =
+    [BODY_GENES, PATTERN_GENES, EYE_COLOUR_GENES];
+
+/// Chance, out of 256, that a gene swaps with the one above it before inheritance.
+pub const SWAP_CHANCE: u8 = 64;
+/// Chance, out of 256, that a combinable gene pair mutates into a rarer gene.
+pub const MUTATION_CHANCE: u8 = 64;
+/// Chance, out of 256, that a gene is taken from the matron rather than the sire.
+pub const MATRON_CHANCE: u8 = 128;
+
+/// Slot produced when genes of slots `a` and `b` combine, if the pair is combinable.
+/// Two common variants give an uncommon one, two uncommon give a rare one and two rare
+/// give the very rare one.
+pub fn mutation(a: usize, b: usize) -> Option<usize> {
+    match (a.min(b), a.max(b)) {
+        (0, 1) => Some(4),
+        (2, 3) => Some(5),
+        (4, 5) => Some(6),
+        (6, 6) => Some(7),
+        _ => None,
+    }
+}
+
+/// Inclusive range of gene bytes expressing the variant in `slot`.
+pub fn slot_range(slot: usize) -> (u8, u8) {
+    let lower = if slot == 0 { 0 } else { GENE_TABLE[slot - 1] + 1 };
+    (lower, GENE_TABLE[slot])
+}
+
+/// Deterministic random stream derived from the breeding seed (SplitMix64).
+pub struct GeneRng {
+    state: u64,
+}
+
+impl GeneRng {
+    pub fn new(seed: &[u8; 16]) -> Self {
+        let mut low = [0u8; 8];
+        let mut high = [0u8; 8];
+        low.copy_from_slice(&seed[..8]);
+        high.copy_from_slice(&seed[8..]);
+        GeneRng {
+            state: u64::from_le_bytes(low) ^ u64::from_le_bytes(high).rotate_left(32),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_byte(&mut self) -> u8 {
+        (self.next_u64() >> 56) as u8
+    }
+
+    /// Returns true with probability `chance / 256`.
+    pub fn chance(&mut self, chance: u8) -> bool {
+        self.next_byte() < chance
+    }
+
+    /// Random gene byte expressing the variant in `slot`.
+    pub fn gene_in(&mut self, slot: usize) -> u8 {
+        let (lower, upper) = slot_range(slot);
+        let size = upper as u64 - lower as u64 + 1;
+        lower + (self.next_u64() % size) as u8
+    }
+}
+
+/// Genes of one trait after recessive genes had the chance to move up.
+fn shuffled_genes(
+    rng: &mut GeneRng,
+    dna: &[u8; 16],
+    genes: &[usize; GENES_PER_TRAIT],
+) -> [u8; GENES_PER_TRAIT] {
+    let mut shuffled = [dna[genes[0]], dna[genes[1]], dna[genes[2]], dna[genes[3]]];
+    // Starting from the most recessive gene, so a gene may climb several positions.
+    for position in (1..GENES_PER_TRAIT).rev() {
+        if rng.chance(SWAP_CHANCE) {
+            shuffled.swap(position, position - 1);
+        }
+    }
+    shuffled
+}
+
+/// DNA of the child of `matron` and `sire`, deterministic for a given `seed`.
+/// Bytes outside the trait genes, including the gender byte, are taken from either parent.
+pub fn breed(matron: &[u8; 16], sire: &[u8; 16], seed: &[u8; 16]) -> [u8; 16] {
+    let mut rng = GeneRng::new(seed);
+    let mut child = [0u8; 16];
+    let mut inherited = [false; 16];
+
+    for genes in TRAIT_GENES.iter() {
+        let from_matron = shuffled_genes(&mut rng, matron, genes);
+        let from_sire = shuffled_genes(&mut rng, sire, genes);
+        for (position, index) in genes.iter().enumerate() {
+            let (a, b) = (from_matron[position], from_sire[position]);
+            let mutated = match mutation(slot_of(a), slot_of(b)) {
+                Some(slot) if rng.chance(MUTATION_CHANCE) => Some(slot),
+                _ => None,
+            };
+            child[*index] = match mutated {
+                Some(slot) => rng.gene_in(slot),
+                None if rng.chance(MATRON_CHANCE) => a,
+                None => b,
+            };
+            inherited[*index] = true;
+        }
+    }
+
+    for (index, gene) in child.iter_mut().enumerate() {
+        if !inherited[index] {
+            *gene = if rng.chance(MATRON_CHANCE) { matron[index] } else { sire[index] };
+        }
+    }
+    child
+}
@@ -0,0 +1,152 @@
+//! Canonical decoding of kitty DNA into visual traits.
+//! Shared by pallet-kitties, its runtime API and the node RPC so every consumer
+//! interprets the 16 DNA bytes the same way, and breeding through [`genome`] so
+//! children inherit genes by the same rules everywhere.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub mod genome;
+#[cfg(test)]
+mod tests;
+
+pub use genome::breed;
+
+/// DNA byte selecting the body; its recessive genes are listed in [`genome::BODY_GENES`].
+pub const BODY_BYTE: usize = 0;
+/// DNA byte selecting the coat pattern.
+pub const PATTERN_BYTE: usize = 1;
+/// DNA byte selecting the eye colour.
+pub const EYE_COLOUR_BYTE: usize = 2;
+/// DNA byte whose lowest bit selects the gender.
+pub const GENDER_BYTE: usize = 15;
+
+/// Inclusive upper bound of the gene byte for each variant slot, in slot order.
+/// Slots 0-3 are common (48/256 each), 4-5 uncommon (24/256), 6 rare (12/256)
+/// and 7 very rare (4/256).
+pub const GENE_TABLE: [u8; 8] = [47, 95, 143, 191, 215, 239, 251, 255];
+
+/// Rarity score contributed by the variant in each slot.
+pub const SLOT_SCORES: [u8; 8] = [0, 0, 0, 0, 1, 1, 2, 3];
+
+/// Slot of the variant expressed by a gene byte.
+pub fn slot_of(gene: u8) -> usize {
+    GENE_TABLE.iter().position(|max| gene <= *max).unwrap_or(GENE_TABLE.len() - 1)
+}
+
+macro_rules! gene_trait {
+    ($(#[$attr:meta])* $name:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$attr])*
+        #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+        #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// All variants in slot order, from most common to rarest.
+            pub const ALL: [$name; 8] = [$($name::$variant),+];
+
+            /// Variant expressed by a gene byte.
+            pub fn from_gene(gene: u8) -> Self {
+                Self::ALL[slot_of(gene)]
+            }
+
+            /// Rarity score of this variant, 0 (common) to 3 (very rare).
+            pub fn rarity_score(self) -> u8 {
+                SLOT_SCORES[self as usize]
+            }
+        }
+    };
+}
+
+gene_trait! {
+    /// Body shape, decoded from `BODY_BYTE`.
+    Body { Shorthair, Persian, Siamese, Ragdoll, MaineCoon, Bengal, Sphynx, Cymric }
+}
+
+gene_trait! {
+    /// Coat pattern, decoded from `PATTERN_BYTE`.
+    Pattern { Solid, Tabby, Spotted, Bicolour, Calico, Tortoiseshell, Tiger, Galaxy }
+}
+
+gene_trait! {
+    /// Eye colour, decoded from `EYE_COLOUR_BYTE`.
+    EyeColour { Amber, Green, Yellow, Copper, Blue, Hazel, Odd, Violet }
+}
+
+/// Gender, decoded from the lowest bit of `GENDER_BYTE`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    pub fn from_dna(dna: &[u8; 16]) -> Self {
+        if dna[GENDER_BYTE] & 1 == 0 {
+            Gender::Male
+        } else {
+            Gender::Female
+        }
+    }
+}
+
+/// Overall rarity tier, from the summed rarity scores of body, pattern and eye colour.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Rarity {
+    /// Score 0-1.
+    Common,
+    /// Score 2-3.
+    Uncommon,
+    /// Score 4-5.
+    Rare,
+    /// Score 6-7.
+    Epic,
+    /// Score 8-9.
+    Legendary,
+}
+
+impl Rarity {
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            0..=1 => Rarity::Common,
+            2..=3 => Rarity::Uncommon,
+            4..=5 => Rarity::Rare,
+            6..=7 => Rarity::Epic,
+            _ => Rarity::Legendary,
+        }
+    }
+}
+
+/// Visual traits of a kitty.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Traits {
+    pub body: Body,
+    pub pattern: Pattern,
+    pub eye_colour: EyeColour,
+    pub gender: Gender,
+    pub rarity: Rarity,
+}
+
+impl Traits {
+    pub fn from_dna(dna: &[u8; 16]) -> Self {
+        let body = Body::from_gene(dna[BODY_BYTE]);
+        let pattern = Pattern::from_gene(dna[PATTERN_BYTE]);
+        let eye_colour = EyeColour::from_gene(dna[EYE_COLOUR_BYTE]);
+        let score = body.rarity_score() + pattern.rarity_score() + eye_colour.rarity_score();
+        Traits {
+            body,
+            pattern,
+            eye_colour,
+            gender: Gender::from_dna(dna),
+            rarity: Rarity::from_score(score),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use super::*;
+use codec::{Decode, Encode};
+
+// 每个槽位对应的字节数量
+const SLOT_SIZES: [usize; 8] = [48, 48, 48, 48, 24, 24, 12, 4];
+
+// 按槽位大小独立计算字节所在槽位
+fn expected_slot(gene: u8) -> usize {
+    let mut upper = 0usize;
+    for (slot, size) in SLOT_SIZES.iter().enumerate() {
+        upper += size;
+        if (gene as usize) < upper {
+            return slot;
+        }
+    }
+    unreachable!("slot sizes cover every byte");
+}
+
+#[test]
+fn gene_table_covers_every_byte() {
+    assert_eq!(SLOT_SIZES.iter().sum::<usize>(), 256);
+    assert!(GENE_TABLE.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(GENE_TABLE[GENE_TABLE.len() - 1], u8::MAX);
+    assert_eq!(GENE_TABLE.len(), SLOT_SCORES.len());
+}
+
+#[test]
+fn slot_of_every_byte_work() {
+    for gene in 0..=u8::MAX {
+        assert_eq!(slot_of(gene), expected_slot(gene), "gene {}", gene);
+    }
+}
+
+#[test]
+fn variants_are_in_slot_order() {
+    for slot in 0..8 {
+        assert_eq!(Body::ALL[slot] as usize, slot);
+        assert_eq!(Pattern::ALL[slot] as usize, slot);
+        assert_eq!(EyeColour::ALL[slot] as usize, slot);
+    }
+}
+
+#[test]
+fn body_table_work() {
+    let mut counts = [0usize; 8];
+    for gene in 0..=u8::MAX {
+        let body = Body::from_gene(gene);
+        assert_eq!(body, Body::ALL[expected_slot(gene)]);
+        counts[body as usize] += 1;
+    }
+    assert_eq!(counts, SLOT_SIZES);
+    assert_eq!(Body::from_gene(0), Body::Shorthair);
+    assert_eq!(Body::from_gene(191), Body::Ragdoll);
+    assert_eq!(Body::from_gene(192), Body::MaineCoon);
+    assert_eq!(Body::from_gene(252), Body::Cymric);
+}
+
+#[test]
+fn pattern_table_work() {
+    let mut counts = [0usize; 8];
+    for gene in 0..=u8::MAX {
+        let pattern = Pattern::from_gene(gene);
+        assert_eq!(pattern, Pattern::ALL[expected_slot(gene)]);
+        counts[pattern as usize] += 1;
+    }
+    assert_eq!(counts, SLOT_SIZES);
+    assert_eq!(Pattern::from_gene(48), Pattern::Tabby);
+    assert_eq!(Pattern::from_gene(240), Pattern::Tiger);
+    assert_eq!(Pattern::from_gene(255), Pattern::Galaxy);
+}
+
+#[test]
+fn eye_colour_table_work() {
+    let mut counts = [0usize; 8];
+    for gene in 0..=u8::MAX {
+        let eye_colour = EyeColour::from_gene(gene);
+        assert_eq!(eye_colour, EyeColour::ALL[expected_slot(gene)]);
+        counts[eye_colour as usize] += 1;
+    }
+    assert_eq!(counts, SLOT_SIZES);
+    assert_eq!(EyeColour::from_gene(143), EyeColour::Yellow);
+    assert_eq!(EyeColour::from_gene(216), EyeColour::Hazel);
+    assert_eq!(EyeColour::from_gene(251), EyeColour::Odd);
+}
+
+#[test]
+fn rarity_score_work() {
+    for (slot, score) in SLOT_SCORES.iter().enumerate() {
+        assert_eq!(Body::ALL[slot].rarity_score(), *score);
+        assert_eq!(Pattern::ALL[slot].rarity_score(), *score);
+        assert_eq!(EyeColour::ALL[slot].rarity_score(), *score);
+    }
+    assert_eq!(Body::Shorthair.rarity_score(), 0);
+    assert_eq!(Pattern::Calico.rarity_score(), 1);
+    assert_eq!(EyeColour::Odd.rarity_score(), 2);
+    assert_eq!(Body::Cymric.rarity_score(), 3);
+}
+
+#[test]
+fn rarity_from_score_work() {
+    let expected = [
+        Rarity::Common,
+        Rarity::Common,
+        Rarity::Uncommon,
+        Rarity::Uncommon,
+        Rarity::Rare,
+        Rarity::Rare,
+        Rarity::Epic,
+        Rarity::Epic,
+        Rarity::Legendary,
+        Rarity::Legendary,
+    ];
+    for (score, rarity) in expected.iter().enumerate() {
+        assert_eq!(Rarity::from_score(score as u8), *rarity);
+    }
+    assert!(Rarity::Common < Rarity::Legendary);
+}
+
+#[test]
+fn rarity_of_every_combination_work() {
+    let mut counts = [0usize; 5];
+    for body in Body::ALL.iter() {
+        for pattern in Pattern::ALL.iter() {
+            for eye_colour in EyeColour::ALL.iter() {
+                let mut dna = [0u8; 16];
+                dna[BODY_BYTE] = GENE_TABLE[*body as usize];
+                dna[PATTERN_BYTE] = GENE_TABLE[*pattern as usize];
+                dna[EYE_COLOUR_BYTE] = GENE_TABLE[*eye_colour as usize];
+                let score =
+                    body.rarity_score() + pattern.rarity_score() + eye_colour.rarity_score();
+                let traits = Traits::from_dna(&dna);
+                assert_eq!(traits.rarity, Rarity::from_score(score));
+                counts[traits.rarity as usize] += 1;
+            }
+        }
+    }
+    assert_eq!(counts.iter().sum::<usize>(), 512);
+    // 至少两项为最稀有、其余一项不低于稀有时才是 Legendary
+    assert_eq!(counts[Rarity::Legendary as usize], 4);
+}
+
+#[test]
+fn gender_of_every_byte_work() {
+    for gene in 0..=u8::MAX {
+        let mut dna = [0u8; 16];
+        dna[GENDER_BYTE] = gene;
+        let expected = if gene % 2 == 0 { Gender::Male } else { Gender::Female };
+        assert_eq!(Gender::from_dna(&dna), expected);
+    }
+}
+
+#[test]
+fn traits_from_dna_work() {
+    let mut dna = [0x55u8; 16];
+    dna[BODY_BYTE] = 255;
+    dna[PATTERN_BYTE] = 250;
+    dna[EYE_COLOUR_BYTE] = 0;
+    dna[GENDER_BYTE] = 1;
+
+    let traits = Traits::from_dna(&dna);
+    assert_eq!(
+        traits,
+        Traits {
+            body: Body::Cymric,
+            pattern: Pattern::Tiger,
+            eye_colour: EyeColour::Amber,
+            gender: Gender::Female,
+            rarity: Rarity::Rare,
+        }
+    );
+
+    // 其余字节不影响特征
+    let mut other = [0xaau8; 16];
+    other[BODY_BYTE] = 255;
+    other[PATTERN_BYTE] = 250;
+    other[EYE_COLOUR_BYTE] = 0;
+    other[GENDER_BYTE] = 1;
+    assert_eq!(Traits::from_dna(&other), traits);
+}
+
+#[test]
+fn traits_codec_work() {
+    let traits = Traits::from_dna(&[0xf0u8; 16]);
+    let encoded = traits.encode();
+    assert_eq!(encoded.len(), 5);
+    assert_eq!(Traits::decode(&mut &encoded[..]).unwrap(), traits);
+}
+
+// 由序号生成不同的繁殖种子
+fn seed_of(index: u64) -> [u8; 16] {
+    let mut seed = [0u8; 16];
+    seed[..8].copy_from_slice(&index.to_le_bytes());
+    seed[8..].copy_from_slice(&index.wrapping_mul(31).to_le_bytes());
+    seed
+}
+
+// 所有特征基因均取指定槽位的最小字节
+fn dna_of_slot(slot: usize) -> [u8; 16] {
+    let mut dna = [0u8; 16];
+    for genes in genome::TRAIT_GENES.iter() {
+        for index in genes.iter() {
+            dna[*index] = genome::slot_range(slot).0;
+        }
+    }
+    dna
+}
+
+#[test]
+fn trait_genes_layout_work() {
+    let mut used = [false; 16];
+    for genes in genome::TRAIT_GENES.iter() {
+        for index in genes.iter() {
+            assert!(!used[*index], "gene byte {} used twice", index);
+            used[*index] = true;
+        }
+    }
+    assert_eq!(genome::BODY_GENES[0], BODY_BYTE);
+    assert_eq!(genome::PATTERN_GENES[0], PATTERN_BYTE);
+    assert_eq!(genome::EYE_COLOUR_GENES[0], EYE_COLOUR_BYTE);
+    assert!(!used[GENDER_BYTE]);
+}
+
+#[test]
+fn slot_range_work() {
+    let mut covered = 0usize;
+    for (slot, size) in SLOT_SIZES.iter().enumerate() {
+        let (lower, upper) = genome::slot_range(slot);
+        assert_eq!(upper as usize - lower as usize + 1, *size);
+        assert_eq!(slot_of(lower), slot);
+        assert_eq!(slot_of(upper), slot);
+        covered += size;
+    }
+    assert_eq!(covered, 256);
+}
+
+#[test]
+fn mutation_table_work() {
+    let mut combinable = 0;
+    for a in 0..8 {
+        for b in 0..8 {
+            assert_eq!(genome::mutation(a, b), genome::mutation(b, a));
+            if let Some(slot) = genome::mutation(a, b) {
+                // 组合结果比两个基因都稀有
+                assert!(slot > a && slot > b || (a == 6 && b == 6 && slot == 7));
+                combinable += 1;
+            }
+        }
+    }
+    // (0,1)、(2,3)、(4,5) 两种顺序及 (6,6)
+    assert_eq!(combinable, 7);
+    assert_eq!(genome::mutation(0, 1), Some(4));
+    assert_eq!(genome::mutation(3, 2), Some(5));
+    assert_eq!(genome::mutation(4, 5), Some(6));
+    assert_eq!(genome::mutation(6, 6), Some(7));
+    assert_eq!(genome::mutation(1, 2), None);
+    assert_eq!(genome::mutation(7, 7), None);
+}
+
+#[test]
+fn gene_rng_work() {
+    let mut first = genome::GeneRng::new(&seed_of(1));
+    let mut second = genome::GeneRng::new(&seed_of(1));
+    for _ in 0..100 {
+        assert_eq!(first.next_u64(), second.next_u64());
+    }
+    assert_ne!(
+        genome::GeneRng::new(&seed_of(1)).next_u64(),
+        genome::GeneRng::new(&seed_of(2)).next_u64()
+    );
+
+    let mut rng = genome::GeneRng::new(&seed_of(3));
+    for slot in 0..GENE_TABLE.len() {
+        for _ in 0..100 {
+            assert_eq!(slot_of(rng.gene_in(slot)), slot);
+        }
+    }
+    assert!(!rng.chance(0));
+}
+
+#[test]
+fn breed_deterministic_work() {
+    let matron = [0x13u8; 16];
+    let sire = [0xc7u8; 16];
+    for index in 0..100 {
+        let seed = seed_of(index);
+        assert_eq!(breed(&matron, &sire, &seed), breed(&matron, &sire, &seed));
+    }
+    assert!((0..100).any(|index| {
+        breed(&matron, &sire, &seed_of(index)) != breed(&matron, &sire, &seed_of(0))
+    }));
+}
+
+#[test]
+fn breed_identical_parents_work() {
+    // 相同槽位的基因不会组合，子代与父母相同
+    let dna = dna_of_slot(2);
+    for index in 0..100 {
+        assert_eq!(breed(&dna, &dna, &seed_of(index)), dna);
+    }
+}
+
+#[test]
+fn breed_genes_come_from_parents_work() {
+    let mut matron = [0u8; 16];
+    let mut sire = [0u8; 16];
+    for (index, (m, s)) in matron.iter_mut().zip(sire.iter_mut()).enumerate() {
+        *m = (index as u8).wrapping_mul(37);
+        *s = (index as u8).wrapping_mul(91).wrapping_add(11);
+    }
+    for seed in 0..1_000 {
+        let child = breed(&matron, &sire, &seed_of(seed));
+        for genes in genome::TRAIT_GENES.iter() {
+            for index in genes.iter() {
+                let gene = child[*index];
+                let from_parent = genes.iter().any(|i| matron[*i] == gene || sire[*i] == gene);
+                let mutated = genes.iter().any(|i| {
+                    genes.iter().any(|j| {
+                        genome::mutation(slot_of(matron[*i]), slot_of(sire[*j]))
+                            == Some(slot_of(gene))
+                    })
+                });
+                assert!(from_parent || mutated, "seed {} gene byte {}", seed, index);
+            }
+        }
+        for (index, gene) in child.iter().enumerate().skip(12) {
+            assert!(*gene == matron[index] || *gene == sire[index]);
+        }
+    }
+}
+
+#[test]
+fn breed_recessive_surface_work() {
+    // 显性基因为槽位0，隐性基因均为槽位3，两者不会组合
+    let mut dna = dna_of_slot(3);
+    for genes in genome::TRAIT_GENES.iter() {
+        dna[genes[0]] = genome::slot_range(0).0;
+    }
+    let surfaced = (0..1_000)
+        .filter(|index| slot_of(breed(&dna, &dna, &seed_of(*index))[BODY_BYTE]) == 3)
+        .count();
+    // 显性基因与第一个隐性基因交换的概率为 1/4
+    assert!(surfaced > 150 && surfaced < 350, "surfaced {}", surfaced);
+}
+
+#[test]
+fn breed_mutation_work() {
+    // 槽位0与槽位1的基因组合为槽位4
+    let matron = dna_of_slot(0);
+    let sire = dna_of_slot(1);
+    let mut mutated = 0;
+    for index in 0..1_000 {
+        let child = breed(&matron, &sire, &seed_of(index));
+        match slot_of(child[BODY_BYTE]) {
+            0 | 1 => {}
+            4 => mutated += 1,
+            slot => panic!("unexpected slot {}", slot),
+        }
+    }
+    assert!(mutated > 150 && mutated < 350, "mutated {}", mutated);
+}
+
+#[test]
+fn breed_gender_from_parents_work() {
+    let mut matron = [0u8; 16];
+    let mut sire = [0u8; 16];
+    matron[GENDER_BYTE] = 1;
+    sire[GENDER_BYTE] = 0;
+    let females = (0..1_000)
+        .map(|index| Gender::from_dna(&breed(&matron, &sire, &seed_of(index))))
+        .filter(|gender| *gender == Gender::Female)
+        .count();
+    assert!(females > 400 && females < 600, "females {}", females);
+}
@@ -0,0 +1,52 @@
+//! Runtime API definition for pallet-kitties.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::{Gender, KittyInfo, KittyMetadata, Traits};
+
+sp_api::decl_runtime_apis! {
+    pub trait KittiesApi<AccountId, KittyIndex, Balance, BlockNumber, AssetId> where
+        AccountId: Codec,
+        KittyIndex: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+        AssetId: Codec,
+    {
+        /// Information about a single kitty, including its lineage.
+        /// Burned kitties are answered from their tombstone, with `burned_at` set.
+        fn kitty(
+            kitty_id: KittyIndex,
+        ) -> Option<KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId>>;
+        /// Kitties owned by `owner` in ascending order, starting after `start_after`.
+        fn kitties_of_owner(
+            owner: AccountId,
+            start_after: Option<KittyIndex>,
+            limit: u32,
+        ) -> Vec<KittyIndex>;
+        /// Kitties currently listed for sale with their price, starting after `start_after`.
+        /// The asset id is set when the price is denominated in that asset rather than the
+        /// native currency.
+        fn kitties_for_sale(
+            start_after: Option<KittyIndex>,
+            limit: u32,
+        ) -> Vec<(KittyIndex, Balance, Option<AssetId>)>;
+        /// Total number of kitties ever created.
+        fn kitties_count() -> KittyIndex;
+        /// Block at which the kitty can breed again; in the past when it is ready now.
+        fn breeding_ready_at(kitty_id: KittyIndex) -> Option<BlockNumber>;
+        /// Gender decoded from the kitty's DNA.
+        fn gender(kitty_id: KittyIndex) -> Option<Gender>;
+        /// Body, pattern, eye colour and rarity tier decoded from the kitty's DNA.
+        fn traits(kitty_id: KittyIndex) -> Option<Traits>;
+        /// Price a buyer would pay at this block, with the asset it is denominated in when
+        /// it is not the native currency; Dutch auctions are interpolated.
+        fn current_price(kitty_id: KittyIndex) -> Option<(Balance, Option<AssetId>)>;
+        /// Name and off-chain metadata URI set by the owner, with the deposit held for them.
+        fn metadata(kitty_id: KittyIndex) -> Option<KittyMetadata<AccountId, Balance>>;
+    }
+}
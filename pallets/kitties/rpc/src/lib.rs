@@ -0,0 +1,242 @@
+//! RPC interface for pallet-kitties.
+//! Exposes the `KittiesApi` runtime API so clients do not need to compute storage keys.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_kitties_rpc_runtime_api::{
+    Gender, KittiesApi as KittiesRuntimeApi, KittyInfo, KittyMetadata, Traits,
+};
+
+/// Balances are returned as [`NumberOrHex`], so values beyond the precision of a
+/// JavaScript number are still represented exactly.
+#[rpc]
+pub trait KittiesApi<BlockHash, AccountId, KittyIndex, BlockNumber, AssetId> {
+    #[rpc(name = "kitties_getKitty")]
+    fn kitty(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<KittyInfo<AccountId, KittyIndex, NumberOrHex, BlockNumber, AssetId>>>;
+
+    #[rpc(name = "kitties_kittiesOfOwner")]
+    fn kitties_of_owner(
+        &self,
+        owner: AccountId,
+        start_after: Option<KittyIndex>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<KittyIndex>>;
+
+    #[rpc(name = "kitties_kittiesForSale")]
+    fn kitties_for_sale(
+        &self,
+        start_after: Option<KittyIndex>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(KittyIndex, NumberOrHex, Option<AssetId>)>>;
+
+    #[rpc(name = "kitties_kittiesCount")]
+    fn kitties_count(&self, at: Option<BlockHash>) -> Result<KittyIndex>;
+
+    #[rpc(name = "kitties_breedingReadyAt")]
+    fn breeding_ready_at(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<BlockNumber>>;
+
+    #[rpc(name = "kitties_gender")]
+    fn gender(&self, kitty_id: KittyIndex, at: Option<BlockHash>) -> Result<Option<Gender>>;
+
+    #[rpc(name = "kitties_traits")]
+    fn traits(&self, kitty_id: KittyIndex, at: Option<BlockHash>) -> Result<Option<Traits>>;
+
+    #[rpc(name = "kitties_currentPrice")]
+    fn current_price(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<(NumberOrHex, Option<AssetId>)>>;
+
+    #[rpc(name = "kitties_metadata")]
+    fn metadata(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Option<KittyMetadata<AccountId, NumberOrHex>>>;
+}
+
+/// A struct that implements the [`KittiesApi`].
+pub struct Kitties<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Kitties<C, B> {
+    /// Create new `Kitties` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+/// Error code for failures inside the runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Unable to query kitties.".into(),
+        data: Some(format!("{:?}", err).into()),
+    }
+}
+
+fn to_rpc_balance<Balance: TryInto<NumberOrHex>>(value: Balance) -> Result<NumberOrHex> {
+    value.try_into().map_err(|_| RpcError {
+        code: ErrorCode::ServerError(RUNTIME_ERROR),
+        message: "Balance doesn't fit in NumberOrHex representation.".into(),
+        data: None,
+    })
+}
+
+impl<C, Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>
+    KittiesApi<<Block as BlockT>::Hash, AccountId, KittyIndex, BlockNumber, AssetId>
+    for Kitties<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync,
+    C::Api: KittiesRuntimeApi<Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>,
+    AccountId: Codec,
+    KittyIndex: Codec,
+    Balance: Codec + TryInto<NumberOrHex>,
+    BlockNumber: Codec,
+    AssetId: Codec,
+{
+    fn kitty(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<KittyInfo<AccountId, KittyIndex, NumberOrHex, BlockNumber, AssetId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let info = api.kitty(&at, kitty_id).map_err(runtime_error)?;
+        info.map(|info| {
+            Ok(KittyInfo {
+                dna: info.dna,
+                owner: info.owner,
+                price: info.price.map(to_rpc_balance).transpose()?,
+                price_asset: info.price_asset,
+                parents: info.parents,
+                generation: info.generation,
+                birth_block: info.birth_block,
+                creator: info.creator,
+                burned_at: info.burned_at,
+            })
+        })
+        .transpose()
+    }
+
+    fn kitties_of_owner(
+        &self,
+        owner: AccountId,
+        start_after: Option<KittyIndex>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<KittyIndex>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.kitties_of_owner(&at, owner, start_after, limit).map_err(runtime_error)
+    }
+
+    fn kitties_for_sale(
+        &self,
+        start_after: Option<KittyIndex>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(KittyIndex, NumberOrHex, Option<AssetId>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.kitties_for_sale(&at, start_after, limit)
+            .map_err(runtime_error)?
+            .into_iter()
+            .map(|(kitty_id, price, asset)| Ok((kitty_id, to_rpc_balance(price)?, asset)))
+            .collect()
+    }
+
+    fn kitties_count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<KittyIndex> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.kitties_count(&at).map_err(runtime_error)
+    }
+
+    fn breeding_ready_at(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.breeding_ready_at(&at, kitty_id).map_err(runtime_error)
+    }
+
+    fn gender(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Gender>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.gender(&at, kitty_id).map_err(runtime_error)
+    }
+
+    fn traits(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Traits>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.traits(&at, kitty_id).map_err(runtime_error)
+    }
+
+    fn current_price(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<(NumberOrHex, Option<AssetId>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let price = api.current_price(&at, kitty_id).map_err(runtime_error)?;
+        price.map(|(price, asset)| Ok((to_rpc_balance(price)?, asset))).transpose()
+    }
+
+    fn metadata(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<KittyMetadata<AccountId, NumberOrHex>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let metadata = api.metadata(&at, kitty_id).map_err(runtime_error)?;
+        metadata
+            .map(|metadata| {
+                Ok(KittyMetadata {
+                    depositor: metadata.depositor,
+                    deposit: to_rpc_balance(metadata.deposit)?,
+                    name: metadata.name,
+                    uri: metadata.uri,
+                })
+            })
+            .transpose()
+    }
+}
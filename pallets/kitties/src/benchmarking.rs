@@ -1,20 +1,473 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-kitties
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use crate::Pallet as KittiesModule;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::{tokens::fungibles, Currency, Get, OnIdle, OnInitialize};
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
+use sp_runtime::{
+    traits::{Bounded, One, Zero},
+    Permill,
+};
+use sp_std::{prelude::*, vec};
+
+const SEED: u32 = 0;
+// 销毁时退还的报价数量上限
+const MAX_OFFERS: u32 = 100;
+
+// 创建账户并充值足够的余额
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, SEED);
+    T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+    who
+}
+
+// 为指定账户创建一个Kitty
+fn create_kitty<T: Config>(owner: &T::AccountId) -> T::KittyIndex {
+    let kitty_id = KittiesCount::<T>::get();
+    KittiesModule::<T>::create(RawOrigin::Signed(owner.clone()).into())
+        .expect("owner is funded; qed");
+    kitty_id
+}
+
+// 由创建者创建Kitty并转移给拥有者，拥有者出售时需支付创建者版税
+fn create_kitty_for<T: Config>(creator: &T::AccountId, owner: &T::AccountId) -> T::KittyIndex {
+    let kitty_id = create_kitty::<T>(creator);
+    KittiesModule::<T>::transfer(
+        RawOrigin::Signed(creator.clone()).into(),
+        owner.clone(),
+        kitty_id,
+    )
+    .expect("creator owns the kitty; qed");
+    KittiesModule::<T>::accept_transfer(RawOrigin::Signed(owner.clone()).into(), kitty_id)
+        .expect("transfer is pending for owner; qed");
+    kitty_id
+}
+
+// 设置Kitty性别，保证繁殖时一雄一雌
+fn set_gender<T: Config>(kitty_id: T::KittyIndex, gender: Gender) {
+    Kitties::<T>::mutate(kitty_id, |kitty| {
+        if let Some(kitty) = kitty {
+            kitty.dna[GENDER_BYTE] = match gender {
+                Gender::Male => 0,
+                Gender::Female => 1,
+            };
+        }
+    });
+}
 
 benchmarks! {
-    do_something {
-        let s in 0 .. 100;
+    create {
         let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), s)
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(Owner::<T>::get(T::KittyIndex::zero()), Some(caller));
+    }
+
+    transfer {
+        let caller = funded_account::<T>("caller", 0);
+        let recipient = funded_account::<T>("recipient", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), recipient.clone(), kitty_id)
+    verify {
+        assert_eq!(PendingTransfers::<T>::get(kitty_id).map(|p| p.to), Some(recipient));
+    }
+
+    accept_transfer {
+        let owner = funded_account::<T>("owner", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        KittiesModule::<T>::transfer(RawOrigin::Signed(owner).into(), caller.clone(), kitty_id)?;
+    }: _(RawOrigin::Signed(caller.clone()), kitty_id)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    reject_transfer {
+        let owner = funded_account::<T>("owner", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        KittiesModule::<T>::transfer(RawOrigin::Signed(owner).into(), caller.clone(), kitty_id)?;
+    }: _(RawOrigin::Signed(caller), kitty_id)
+    verify {
+        assert!(PendingTransfers::<T>::get(kitty_id).is_none());
+    }
+
+    cancel_transfer {
+        let caller = funded_account::<T>("caller", 0);
+        let recipient = funded_account::<T>("recipient", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        KittiesModule::<T>::transfer(RawOrigin::Signed(caller.clone()).into(), recipient, kitty_id)?;
+    }: _(RawOrigin::Signed(caller), kitty_id)
+    verify {
+        assert!(PendingTransfers::<T>::get(kitty_id).is_none());
+    }
+
+    // 最坏情况：父方属于他人并通过授权繁殖
+    bread {
+        let caller = funded_account::<T>("caller", 0);
+        let sire_owner = funded_account::<T>("sire_owner", 0);
+        let matron = create_kitty::<T>(&caller);
+        let sire = create_kitty::<T>(&sire_owner);
+        set_gender::<T>(matron, Gender::Female);
+        set_gender::<T>(sire, Gender::Male);
+        KittiesModule::<T>::approve_siring(
+            RawOrigin::Signed(sire_owner).into(),
+            sire,
+            caller.clone(),
+        )?;
+        let kitty_id = KittiesCount::<T>::get();
+    }: _(RawOrigin::Signed(caller.clone()), matron, sire)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    approve_siring {
+        let caller = funded_account::<T>("caller", 0);
+        let who: T::AccountId = account("who", 0, SEED);
+        let kitty_id = create_kitty::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), kitty_id, who.clone())
+    verify {
+        assert_eq!(SireApprovals::<T>::get(kitty_id), Some(who));
+    }
+
+    revoke_siring {
+        let caller = funded_account::<T>("caller", 0);
+        let who: T::AccountId = account("who", 0, SEED);
+        let kitty_id = create_kitty::<T>(&caller);
+        KittiesModule::<T>::approve_siring(RawOrigin::Signed(caller.clone()).into(), kitty_id, who)?;
+    }: _(RawOrigin::Signed(caller), kitty_id)
+    verify {
+        assert!(SireApprovals::<T>::get(kitty_id).is_none());
+    }
+
+    approve {
+        let caller = funded_account::<T>("caller", 0);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let kitty_id = create_kitty::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), kitty_id, Some(spender.clone()))
+    verify {
+        assert_eq!(Approvals::<T>::get(kitty_id), Some(spender));
+    }
+
+    set_approval_for_all {
+        let caller = funded_account::<T>("caller", 0);
+        let operator: T::AccountId = account("operator", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+    verify {
+        assert!(KittiesModule::<T>::is_operator(&caller, &operator));
+    }
+
+    transfer_from {
+        let owner = funded_account::<T>("owner", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        KittiesModule::<T>::approve(
+            RawOrigin::Signed(owner.clone()).into(),
+            kitty_id,
+            Some(caller.clone()),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), owner, caller.clone(), kitty_id)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    list_sire {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        let price: BalanceOf<T> = 100u32.into();
+    }: _(RawOrigin::Signed(caller), kitty_id, Some(price))
+    verify {
+        assert_eq!(SirePrices::<T>::get(kitty_id), Some(price));
+    }
+
+    buy_siring {
+        let caller = funded_account::<T>("caller", 0);
+        let sire_owner = funded_account::<T>("sire_owner", 0);
+        let matron = create_kitty::<T>(&caller);
+        let sire = create_kitty::<T>(&sire_owner);
+        set_gender::<T>(matron, Gender::Female);
+        set_gender::<T>(sire, Gender::Male);
+        KittiesModule::<T>::list_sire(
+            RawOrigin::Signed(sire_owner).into(),
+            sire,
+            Some(100u32.into()),
+        )?;
+        let kitty_id = KittiesCount::<T>::get();
+    }: _(RawOrigin::Signed(caller.clone()), matron, sire)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    sale {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        let price: BalanceOf<T> = 100u32.into();
+    }: _(RawOrigin::Signed(caller), kitty_id, Some(price))
+    verify {
+        assert_eq!(KittyPrices::<T>::get(kitty_id), Some(price));
+    }
+
+    sale_in_asset {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        let price: BalanceOf<T> = 100u32.into();
+    }: _(RawOrigin::Signed(caller), kitty_id, Default::default(), price)
+    verify {
+        assert_eq!(KittyPrices::<T>::get(kitty_id), Some(price));
+        assert!(KittyPriceAssets::<T>::contains_key(kitty_id));
+    }
+
+    // 最坏情况：按荷兰式拍卖计算价格，卖方不是创建者，需支付版税
+    buy {
+        let creator = funded_account::<T>("creator", 0);
+        let seller = funded_account::<T>("seller", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty_for::<T>(&creator, &seller);
+        KittiesModule::<T>::create_dutch_auction(
+            RawOrigin::Signed(seller).into(),
+            kitty_id,
+            200u32.into(),
+            100u32.into(),
+            T::MaxAuctionDuration::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), kitty_id)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    // 最坏情况：以资产结算，卖方不是创建者，需支付版税
+    buy_in_asset {
+        let creator = funded_account::<T>("creator", 0);
+        let seller = funded_account::<T>("seller", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty_for::<T>(&creator, &seller);
+        let asset_id: AssetIdOf<T> = Default::default();
+        T::BenchmarkHelper::create_asset(asset_id);
+        <T::Assets as fungibles::Mutate<_>>::mint_into(asset_id, &caller, 1_000_000u32.into())?;
+        KittiesModule::<T>::sale_in_asset(
+            RawOrigin::Signed(seller).into(),
+            kitty_id,
+            asset_id,
+            1_000u32.into(),
+        )?;
+    }: buy(RawOrigin::Signed(caller.clone()), kitty_id)
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    create_auction {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(100u32.into()))?;
+    }: _(RawOrigin::Signed(caller), kitty_id, 100u32.into(), T::MaxAuctionDuration::get())
+    verify {
+        assert!(Auctions::<T>::contains_key(kitty_id));
+    }
+
+    // 最坏情况：退还上一出价并触发防狙击延长
+    bid {
+        let seller = funded_account::<T>("seller", 0);
+        let bidder = funded_account::<T>("bidder", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&seller);
+        KittiesModule::<T>::create_auction(
+            RawOrigin::Signed(seller).into(),
+            kitty_id,
+            100u32.into(),
+            One::one(),
+        )?;
+        KittiesModule::<T>::bid(RawOrigin::Signed(bidder).into(), kitty_id, 100u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), kitty_id, 200u32.into())
+    verify {
+        let auction = Auctions::<T>::get(kitty_id).unwrap();
+        assert_eq!(auction.highest_bid, Some((caller, 200u32.into())));
+    }
+
+    settle_auction {
+        let seller = funded_account::<T>("seller", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&seller);
+        KittiesModule::<T>::create_auction(
+            RawOrigin::Signed(seller).into(),
+            kitty_id,
+            100u32.into(),
+            One::one(),
+        )?;
+        KittiesModule::<T>::bid(RawOrigin::Signed(caller.clone()).into(), kitty_id, 100u32.into())?;
+        let end_block = Auctions::<T>::get(kitty_id).unwrap().end_block;
+    }: {
+        KittiesModule::<T>::on_initialize(end_block);
+    }
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(caller));
+    }
+
+    create_dutch_auction {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), kitty_id, 200u32.into(), 100u32.into(), T::MaxAuctionDuration::get())
+    verify {
+        assert!(DutchAuctions::<T>::contains_key(kitty_id));
+    }
+
+    make_offer {
+        let owner = funded_account::<T>("owner", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        let expiry = T::MaxOfferDuration::get();
+        // 替换已有报价
+        KittiesModule::<T>::make_offer(
+            RawOrigin::Signed(caller.clone()).into(),
+            kitty_id,
+            100u32.into(),
+            expiry,
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), kitty_id, 200u32.into(), expiry)
+    verify {
+        assert_eq!(Offers::<T>::get(kitty_id, &caller).map(|o| o.amount), Some(200u32.into()));
+    }
+
+    accept_offer {
+        let caller = funded_account::<T>("caller", 0);
+        let bidder = funded_account::<T>("bidder", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        KittiesModule::<T>::make_offer(
+            RawOrigin::Signed(bidder.clone()).into(),
+            kitty_id,
+            100u32.into(),
+            T::MaxOfferDuration::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller), kitty_id, bidder.clone())
+    verify {
+        assert_eq!(Owner::<T>::get(kitty_id), Some(bidder));
+    }
+
+    cancel_offer {
+        let owner = funded_account::<T>("owner", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        KittiesModule::<T>::make_offer(
+            RawOrigin::Signed(caller.clone()).into(),
+            kitty_id,
+            100u32.into(),
+            T::MaxOfferDuration::get(),
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), kitty_id)
+    verify {
+        assert!(!Offers::<T>::contains_key(kitty_id, &caller));
+    }
+
+    // 单个失效报价的清理
+    expire_offer {
+        let owner = funded_account::<T>("owner", 0);
+        let bidder = funded_account::<T>("bidder", 0);
+        let kitty_id = create_kitty::<T>(&owner);
+        let now = frame_system::Pallet::<T>::block_number();
+        KittiesModule::<T>::make_offer(
+            RawOrigin::Signed(bidder.clone()).into(),
+            kitty_id,
+            100u32.into(),
+            now,
+        )?;
+        OfferCleanupCursor::<T>::put(now);
+        let next = now + One::one();
+    }: {
+        KittiesModule::<T>::on_idle(next, Weight::max_value());
+    }
+    verify {
+        assert!(!Offers::<T>::contains_key(kitty_id, &bidder));
+    }
+
+    set_kitty_reserve {
+        let amount: BalanceOf<T> = 100u32.into();
+    }: _(RawOrigin::Root, amount)
+    verify {
+        assert_eq!(KittyReserveAmount::<T>::get(), amount);
+    }
+
+    set_marketplace_fee {
+        let fee = Permill::from_percent(1);
+    }: _(RawOrigin::Root, fee)
+    verify {
+        assert_eq!(MarketplaceFeeRate::<T>::get(), fee);
+    }
+
+    set_creator_royalty {
+        let royalty = Permill::from_percent(1);
+    }: _(RawOrigin::Root, royalty)
+    verify {
+        assert_eq!(CreatorRoyaltyRate::<T>::get(), royalty);
+    }
+
+    set_max_kitties_per_account {
+        let max = T::MaxKittiesPerAccount::get();
+    }: _(RawOrigin::Root, max)
+    verify {
+        assert_eq!(MaxKittiesPerAccountLimit::<T>::get(), max);
+    }
+
+    set_metadata {
+        let n in 0 .. T::StringLimit::get();
+        let u in 0 .. T::StringLimit::get();
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        let name = vec![0u8; n as usize];
+        let uri = vec![0u8; u as usize];
+    }: _(RawOrigin::Signed(caller), kitty_id, name.clone(), uri.clone())
+    verify {
+        let metadata = Metadata::<T>::get(kitty_id).unwrap();
+        assert_eq!(metadata.name, name);
+        assert_eq!(metadata.uri, uri);
+    }
+
+    clear_metadata {
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        let limit = T::StringLimit::get() as usize;
+        KittiesModule::<T>::set_metadata(
+            RawOrigin::Signed(caller.clone()).into(),
+            kitty_id,
+            vec![0u8; limit],
+            vec![0u8; limit],
+        )?;
+    }: _(RawOrigin::Signed(caller), kitty_id)
+    verify {
+        assert_eq!(Metadata::<T>::get(kitty_id), None);
+    }
+
+    burn {
+        let o in 0 .. MAX_OFFERS;
+        let caller = funded_account::<T>("caller", 0);
+        let kitty_id = create_kitty::<T>(&caller);
+        for i in 0 .. o {
+            let bidder = funded_account::<T>("bidder", i);
+            KittiesModule::<T>::make_offer(
+                RawOrigin::Signed(bidder).into(),
+                kitty_id,
+                100u32.into(),
+                T::MaxOfferDuration::get(),
+            )?;
+        }
+        KittiesModule::<T>::sale(RawOrigin::Signed(caller.clone()).into(), kitty_id, Some(100u32.into()))?;
+        let limit = T::StringLimit::get() as usize;
+        KittiesModule::<T>::set_metadata(
+            RawOrigin::Signed(caller.clone()).into(),
+            kitty_id,
+            vec![0u8; limit],
+            vec![0u8; limit],
+        )?;
+    }: _(RawOrigin::Signed(caller), kitty_id, o)
     verify {
-        assert_eq!(Something::<T>::get(), Some(s));
+        assert_eq!(Owner::<T>::get(kitty_id), None);
+        assert!(Tombstones::<T>::contains_key(kitty_id));
+        assert_eq!(Offers::<T>::iter_prefix(kitty_id).count(), 0);
+        assert_eq!(Metadata::<T>::get(kitty_id), None);
     }
 }
 
-impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test,);
+impl_benchmark_test_suite!(KittiesModule, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -8,25 +8,225 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::traits::ExistenceRequirement;
-    use frame_support::{dispatch::DispatchResult, pallet_prelude::*, traits::Randomness};
+    use frame_support::traits::{
+        tokens::fungibles, ExistenceRequirement, Imbalance, OnUnbalanced, WithdrawReasons,
+    };
+    use frame_support::{
+        dispatch::DispatchResult, pallet_prelude::*, traits::Randomness, transactional,
+    };
 
     use codec::{Decode, Encode};
     use frame_system::pallet_prelude::*;
     use sp_io::hashing::blake2_128;
-    use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded, One};
-    use sp_std::prelude::*;
+    use sp_runtime::{
+        helpers_128bit::multiply_by_rational,
+        traits::{AtLeast32BitUnsigned, Bounded, One, SaturatedConversion, Saturating, Zero},
+        Permill, RuntimeDebug,
+    };
+    use sp_std::{convert::TryFrom, prelude::*};
+
+    #[cfg(feature = "std")]
+    use serde::{Deserialize, Serialize};
 
     use frame_support::traits::Currency;
-    use frame_support::traits::ReservableCurrency;
+    use frame_support::traits::NamedReservableCurrency;
+
+    pub use crate::weights::WeightInfo;
+
+    // 本模块所有质押使用的命名质押标识
+    pub const RESERVE_ID: [u8; 8] = *b"kitties/";
+
+    // Kitty及其血统信息
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Kitty<AccountId, KittyIndex, BlockNumber> {
+        pub dna: [u8; 16],
+        // 父母 (matron, sire)，创建及创世的Kitty没有父母
+        pub parents: Option<(KittyIndex, KittyIndex)>,
+        // 代数，父母代数的最大值 + 1
+        pub generation: u32,
+        pub birth_block: BlockNumber,
+        pub creator: AccountId,
+    }
+
+    impl<AccountId, KittyIndex, BlockNumber> Kitty<AccountId, KittyIndex, BlockNumber> {
+        pub fn gender(&self) -> Gender {
+            Gender::from_dna(&self.dna)
+        }
+
+        pub fn traits(&self) -> Traits {
+            Traits::from_dna(&self.dna)
+        }
+    }
+
+    // 性别及外观特征统一由 kitty-genetics 从DNA解码
+    pub use kitty_genetics::{Gender, Traits, GENDER_BYTE};
+
+    pub type KittyOf<T> = Kitty<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KittyIndex,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    // 已销毁Kitty的墓碑，保留血统信息供后代查询
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Tombstone<AccountId, KittyIndex, BlockNumber> {
+        pub kitty: Kitty<AccountId, KittyIndex, BlockNumber>,
+        // 销毁前的最后拥有者
+        pub owner: AccountId,
+        pub burned_at: BlockNumber,
+    }
+
+    pub type TombstoneOf<T> = Tombstone<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KittyIndex,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    // 繁殖冷却状态
+    #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+    pub struct BreedingCooldown<BlockNumber> {
+        // 已繁殖次数
+        pub breed_count: u32,
+        // 可再次繁殖的区块
+        pub ready_at: BlockNumber,
+    }
+
+    // 存储版本，用于升级时判断是否需要迁移
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+    pub enum Releases {
+        // Kitty 仅包含DNA
+        V1,
+        // Kitty 记录父母、代数、出生区块及创建者
+        V2,
+        // 质押以 RESERVE_ID 命名，每个Kitty实际质押数量记录于 KittyDeposits
+        V3,
+        // Kitties、Owner、KittyPrices 改为 OptionQuery
+        V4,
+        // 由 Owner 补建拥有索引 OwnedKitties 及 OwnedKittiesCount
+        V5,
+    }
+
+    impl Default for Releases {
+        fn default() -> Self {
+            Releases::V1
+        }
+    }
+
+    // 等待接收方确认的转移
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct PendingTransfer<AccountId, BlockNumber> {
+        pub from: AccountId,
+        pub to: AccountId,
+        pub expires_at: BlockNumber,
+    }
+
+    // 英式拍卖，最高出价者在结束区块获得Kitty
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Auction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        // 最低成交价
+        pub reserve_price: Balance,
+        pub end_block: BlockNumber,
+        // 当前最高出价 (出价者, 金额)
+        pub highest_bid: Option<(AccountId, Balance)>,
+        // 最高出价者随出价质押的Kitty质押，成交后转为Kitty质押
+        pub bid_deposit: Balance,
+    }
+
+    pub type AuctionOf<T> = Auction<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    // 荷兰式拍卖，挂售价格在起止区块间由起始价线性下降至结束价
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct DutchAuction<Balance, BlockNumber> {
+        pub start_price: Balance,
+        pub end_price: Balance,
+        pub start_block: BlockNumber,
+        pub end_block: BlockNumber,
+    }
+
+    pub type DutchAuctionOf<T> =
+        DutchAuction<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
-    #[derive(Encode, Decode)]
-    pub struct Kitty(pub [u8; 16]);
+    // 买方对Kitty的报价，报价金额及Kitty质押在报价时质押
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Offer<Balance, BlockNumber> {
+        pub amount: Balance,
+        // 报价在该区块(含)之后失效
+        pub expires_at: BlockNumber,
+        // 随报价质押的Kitty质押，成交后转为Kitty质押
+        pub deposit: Balance,
+    }
+
+    pub type OfferOf<T> = Offer<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+    // Kitty名称及链下元数据URI，质押由设置者支付，拥有者变更后仍退还给设置者
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct KittyMetadata<AccountId, Balance> {
+        pub depositor: AccountId,
+        pub deposit: Balance,
+        pub name: Vec<u8>,
+        pub uri: Vec<u8>,
+    }
+
+    pub type KittyMetadataOf<T> =
+        KittyMetadata<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+    // 供 runtime API / RPC 查询的Kitty信息
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+    pub struct KittyInfo<AccountId, KittyIndex, Balance, BlockNumber, AssetId> {
+        pub dna: [u8; 16],
+        pub owner: AccountId,
+        pub price: Option<Balance>,
+        // 以资产挂售时价格所使用的资产，None 为以 T::Currency 计价
+        pub price_asset: Option<AssetId>,
+        pub parents: Option<(KittyIndex, KittyIndex)>,
+        pub generation: u32,
+        pub birth_block: BlockNumber,
+        pub creator: AccountId,
+        // 已销毁的Kitty记录销毁区块，owner 为销毁前的最后拥有者
+        pub burned_at: Option<BlockNumber>,
+    }
+
+    pub type KittyInfoOf<T> = KittyInfo<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::KittyIndex,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+        AssetIdOf<T>,
+    >;
 
-    type BalanceOf<T> =
+    pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+    pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::AssetId;
+    pub type AssetBalanceOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    // 基准测试中准备以资产结算所需的资产
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<AssetId> {
+        // 创建可供任意账户持有的资产，之后通过 fungibles::Mutate 铸造
+        fn create_asset(asset_id: AssetId);
+    }
 
     #[pallet::config]
     pub trait Config: frame_system::Config {
@@ -36,21 +236,138 @@ pub mod pallet {
         // 定义 KittyIndex 类型，要求实现指定的 trait
         type KittyIndex: Parameter + AtLeast32BitUnsigned + Default + Copy + Bounded;
 
-        // 创建Kitty需要质押数量
+        // 创建Kitty需要质押数量的初始值，可由 ForceOrigin 修改
         type KittyReserve: Get<BalanceOf<Self>>;
 
-        // Currency 类型，用于质押等于资产相关的操作
-        type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+        // Currency 类型，用于质押等于资产相关的操作，质押以 RESERVE_ID 命名
+        type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+
+        // 转移请求的有效区块数，超过后接收方无法确认
+        type TransferExpiry: Get<Self::BlockNumber>;
+
+        // 繁殖基础冷却区块数，随繁殖次数及代数翻倍增长
+        type BaseCooldown: Get<Self::BlockNumber>;
+
+        // 繁殖冷却区块数上限
+        type MaxCooldown: Get<Self::BlockNumber>;
+
+        // 拍卖最长持续区块数
+        type MaxAuctionDuration: Get<Self::BlockNumber>;
+
+        // 防狙击延长区块数，结束前该区块数内的出价会将结束区块延后至出价区块 + 该值
+        type AuctionExtension: Get<Self::BlockNumber>;
+
+        // 每个区块最多结算的英式拍卖数量，超出部分顺延至之后的区块结算
+        type MaxAuctionsPerBlock: Get<u32>;
+
+        // 报价最长有效区块数
+        type MaxOfferDuration: Get<Self::BlockNumber>;
+
+        // 每个账户最多拥有的Kitty数量的初始值及上限，同时限定拥有索引的大小，
+        // 可由 ForceOrigin 在该上限内调低
+        type MaxKittiesPerAccount: Get<u32>;
+
+        // 名称及元数据URI的最大字节数
+        type StringLimit: Get<u32>;
+
+        // 设置元数据的基础质押
+        type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+        // 名称及元数据URI每字节的额外质押
+        type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+        // 市场手续费比例的初始值，从成交价中扣除，可由 ForceOrigin 修改
+        type MarketplaceFee: Get<Permill>;
+
+        // 创建者版税比例的初始值，从成交价中扣除并支付给铸造或繁殖该Kitty的账户，可由 ForceOrigin 修改
+        type CreatorRoyalty: Get<Permill>;
+
+        // 市场手续费的去向
+        type OnMarketplaceFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        // 以资产成交时接收市场手续费的账户
+        type MarketplaceAccount: Get<Self::AccountId>;
+
+        // 可用于Kitty定价及结算的资产，如 pallet_assets
+        type Assets: fungibles::Transfer<Self::AccountId> + fungibles::Mutate<Self::AccountId>;
+
+        // 可修改链上参数的来源，如 root
+        type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+        // 基准测试中创建资产
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<
+            <Self::Assets as fungibles::Inspect<Self::AccountId>>::AssetId,
+        >;
+
+        // Benchmarking result
+        type WeightInfo: WeightInfo;
     }
 
+    // 事件索引按声明顺序分配，新增事件须追加在末尾，不可插入已有事件之间
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         KittyCreate(T::AccountId, T::KittyIndex),
         KittyTransfer(T::AccountId, T::AccountId, T::KittyIndex),
-        KittyForSale(T::AccountId, T::KittyIndex, Option<BalanceOf<T>>),
-        KittySaleOut(T::AccountId, T::KittyIndex, Option<BalanceOf<T>>),
+        KittyForSale(T::AccountId, T::KittyIndex, BalanceOf<T>),
+        // (买方, kitty_id, 成交价, 市场手续费, 创建者版税)
+        KittySaleOut(T::AccountId, T::KittyIndex, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        KittyTransferOffered(T::AccountId, T::AccountId, T::KittyIndex, T::BlockNumber),
+        KittyTransferRejected(T::AccountId, T::KittyIndex),
+        KittyTransferCancelled(T::AccountId, T::KittyIndex),
+        SiringApproved(T::AccountId, T::KittyIndex, T::AccountId),
+        SiringRevoked(T::AccountId, T::KittyIndex),
+        SireForSale(T::AccountId, T::KittyIndex, BalanceOf<T>),
+        SireListingCancelled(T::AccountId, T::KittyIndex),
+        SiringPurchased(T::AccountId, T::KittyIndex, T::KittyIndex, BalanceOf<T>),
+        KittyForSaleInAsset(T::AccountId, T::KittyIndex, AssetIdOf<T>, BalanceOf<T>),
+        KittySaleCancelled(T::AccountId, T::KittyIndex),
+        KittyBurned(T::AccountId, T::KittyIndex),
+        KittyApproved(T::AccountId, T::AccountId, T::KittyIndex),
+        KittyApprovalCleared(T::AccountId, T::KittyIndex),
+        ApprovalForAll(T::AccountId, T::AccountId, bool),
+        AuctionCreated(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+        BidPlaced(T::AccountId, T::KittyIndex, BalanceOf<T>),
+        AuctionExtended(T::KittyIndex, T::BlockNumber),
+        // (卖方, 买方, kitty_id, 成交价, 市场手续费, 创建者版税)
+        AuctionSettled(
+            T::AccountId,
+            T::AccountId,
+            T::KittyIndex,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        AuctionExpired(T::AccountId, T::KittyIndex),
+        DutchAuctionCreated(
+            T::AccountId,
+            T::KittyIndex,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            T::BlockNumber,
+        ),
+        OfferMade(T::AccountId, T::KittyIndex, BalanceOf<T>, T::BlockNumber),
+        // (卖方, 买方, kitty_id, 成交价, 市场手续费, 创建者版税)
+        OfferAccepted(
+            T::AccountId,
+            T::AccountId,
+            T::KittyIndex,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        OfferCancelled(T::AccountId, T::KittyIndex),
+        OfferExpired(T::AccountId, T::KittyIndex),
+        KittyReserveSet(BalanceOf<T>),
+        MarketplaceFeeSet(Permill),
+        CreatorRoyaltySet(Permill),
+        MetadataSet(T::AccountId, T::KittyIndex, Vec<u8>, Vec<u8>),
+        MetadataCleared(T::AccountId, T::KittyIndex),
+        // 最高出价者的质押不足以支付出价，出价已退还，拍卖作废 (卖方, 最高出价者, kitty_id)
+        AuctionSettlementFailed(T::AccountId, T::AccountId, T::KittyIndex),
+        MaxKittiesPerAccountSet(u32),
     }
 
     #[pallet::error]
@@ -62,6 +379,32 @@ pub mod pallet {
         MoneyNotEnough,
         AlreadyOwned,
         NotForSale,
+        NoPendingTransfer,
+        NotTransferRecipient,
+        TransferExpired,
+        NotSireApproved,
+        KittyOnCooldown,
+        IncompatibleGender,
+        SireNotListed,
+        NotApproved,
+        InvalidAuctionDuration,
+        KittyInAuction,
+        NotInAuction,
+        AuctionEnded,
+        BidTooLow,
+        InvalidPriceRange,
+        InvalidOfferExpiry,
+        NoOffer,
+        OfferExpired,
+        PriceOverflow,
+        InvalidFeeRate,
+        TooManyKitties,
+        BadMetadata,
+        NoMetadata,
+        // 传入的报价数量小于该Kitty实际的报价数量
+        OfferWitnessTooLow,
+        // 每个账户的拥有上限超过 T::MaxKittiesPerAccount
+        MaxKittiesTooHigh,
     }
 
     #[pallet::pallet]
@@ -75,42 +418,376 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn kitties)]
     pub type Kitties<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<Kitty>, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, KittyOf<T>, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn owner)]
     pub type Owner<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<T::AccountId>, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn kitty_prices)]
     pub type KittyPrices<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::KittyIndex, Option<BalanceOf<T>>, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, BalanceOf<T>, OptionQuery>;
+
+    // 以资产计价的挂售所使用的资产，不存在时以 T::Currency 计价
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_price_assets)]
+    pub type KittyPriceAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, AssetIdOf<T>, OptionQuery>;
+
+    // 每个Kitty由拥有者实际质押的数量，解除质押时以此为准
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_deposits)]
+    pub type KittyDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, BalanceOf<T>, ValueQuery>;
+
+    // Kitty名称及元数据URI
+    #[pallet::storage]
+    #[pallet::getter(fn metadata)]
+    pub type Metadata<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, KittyMetadataOf<T>, OptionQuery>;
+
+    // 等待接收方确认的转移请求
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfers)]
+    pub type PendingTransfers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        PendingTransfer<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    // 允许他人作为父方(sire)使用的Kitty，使用一次后失效
+    #[pallet::storage]
+    #[pallet::getter(fn sire_approvals)]
+    pub type SireApprovals<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+    // 被授权可转移该Kitty的账户，拥有者变更时清除
+    #[pallet::storage]
+    #[pallet::getter(fn approvals)]
+    pub type Approvals<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, T::AccountId, OptionQuery>;
+
+    // 被授权可转移拥有者全部Kitty的操作者 (owner, operator)
+    #[pallet::storage]
+    #[pallet::getter(fn operator_approvals)]
+    pub type OperatorApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    // 父方(sire)配种服务价格
+    #[pallet::storage]
+    #[pallet::getter(fn sire_prices)]
+    pub type SirePrices<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, BalanceOf<T>, OptionQuery>;
+
+    // Kitty繁殖冷却状态
+    #[pallet::storage]
+    #[pallet::getter(fn cooldowns)]
+    pub type Cooldowns<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        BreedingCooldown<T::BlockNumber>,
+        ValueQuery,
+    >;
+
+    // 已销毁Kitty的墓碑
+    #[pallet::storage]
+    #[pallet::getter(fn tombstones)]
+    pub type Tombstones<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, TombstoneOf<T>, OptionQuery>;
+
+    // 拍卖中的Kitty
+    #[pallet::storage]
+    #[pallet::getter(fn auctions)]
+    pub type Auctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, AuctionOf<T>, OptionQuery>;
+
+    // 荷兰式拍卖的降价计划，挂售价格仍记录于 KittyPrices
+    #[pallet::storage]
+    #[pallet::getter(fn dutch_auctions)]
+    pub type DutchAuctions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::KittyIndex, DutchAuctionOf<T>, OptionQuery>;
+
+    // 按结束区块索引的拍卖 (end_block, kitty_id)，用于在区块初始化时结算
+    #[pallet::storage]
+    pub type AuctionEndings<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Blake2_128Concat,
+        T::KittyIndex,
+        (),
+        OptionQuery,
+    >;
+
+    // 买方报价 (kitty_id, bidder)
+    #[pallet::storage]
+    #[pallet::getter(fn offers)]
+    pub type Offers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        Blake2_128Concat,
+        T::AccountId,
+        OfferOf<T>,
+        OptionQuery,
+    >;
+
+    // 按失效区块索引的报价 (expires_at, (kitty_id, bidder))，由 on_idle 清理
+    #[pallet::storage]
+    pub type OfferExpiries<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Blake2_128Concat,
+        (T::KittyIndex, T::AccountId),
+        (),
+        OptionQuery,
+    >;
+
+    // 下一个待结算拍卖的区块
+    #[pallet::storage]
+    pub type AuctionSettlementCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    // 下一个待清理失效报价的区块
+    #[pallet::storage]
+    pub type OfferCleanupCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    // 账户拥有的Kitty索引 (owner, kitty_id)
+    #[pallet::storage]
+    #[pallet::getter(fn owned_kitties)]
+    pub type OwnedKitties<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::KittyIndex,
+        (),
+        OptionQuery,
+    >;
+
+    // 账户拥有的Kitty数量
+    #[pallet::storage]
+    #[pallet::getter(fn owned_kitties_count)]
+    pub type OwnedKittiesCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultKittyReserve<T: Config>() -> BalanceOf<T> {
+        T::KittyReserve::get()
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultMaxKittiesPerAccount<T: Config>() -> u32 {
+        T::MaxKittiesPerAccount::get()
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultMarketplaceFee<T: Config>() -> Permill {
+        T::MarketplaceFee::get()
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultCreatorRoyalty<T: Config>() -> Permill {
+        T::CreatorRoyalty::get()
+    }
+
+    // 当前Kitty质押数量，未设置时为 T::KittyReserve
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_reserve)]
+    pub type KittyReserveAmount<T: Config> =
+        StorageValue<_, BalanceOf<T>, ValueQuery, DefaultKittyReserve<T>>;
+
+    // 当前每个账户最多拥有的Kitty数量，未设置时为 T::MaxKittiesPerAccount
+    #[pallet::storage]
+    #[pallet::getter(fn max_kitties_per_account)]
+    pub type MaxKittiesPerAccountLimit<T: Config> =
+        StorageValue<_, u32, ValueQuery, DefaultMaxKittiesPerAccount<T>>;
+
+    // 当前市场手续费比例，未设置时为 T::MarketplaceFee
+    #[pallet::storage]
+    #[pallet::getter(fn marketplace_fee)]
+    pub type MarketplaceFeeRate<T: Config> =
+        StorageValue<_, Permill, ValueQuery, DefaultMarketplaceFee<T>>;
+
+    // 当前创建者版税比例，未设置时为 T::CreatorRoyalty
+    #[pallet::storage]
+    #[pallet::getter(fn creator_royalty)]
+    pub type CreatorRoyaltyRate<T: Config> =
+        StorageValue<_, Permill, ValueQuery, DefaultCreatorRoyalty<T>>;
+
+    // 创世Kitty列表 (拥有者, DNA)，未指定DNA时由拥有者及索引生成
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub kitties: Vec<(T::AccountId, Option<[u8; 16]>)>,
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self { kitties: Vec::new() }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            for (owner, dna) in &self.kitties {
+                let kitty_id =
+                    Pallet::<T>::get_kitty_id().expect("genesis kitties must not overflow KittyIndex");
+                let dna = dna.unwrap_or_else(|| {
+                    (b"kitties/genesis", owner, kitty_id).using_encoded(blake2_128)
+                });
+
+                Pallet::<T>::ensure_can_own(owner)
+                    .expect("genesis kitty owner must not exceed MaxKittiesPerAccount");
+                // 质押资产
+                Pallet::<T>::reserve_deposit(owner, kitty_id)
+                    .expect("genesis kitty owner must be able to pay KittyReserve");
+
+                Pallet::<T>::mint(owner, kitty_id, dna, None, 0);
+            }
+
+            StorageVersion::<T>::put(Releases::V5);
+        }
+    }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        // 结算已结束的拍卖，每个区块最多结算 MaxAuctionsPerBlock 个，未结算的顺延至下一区块
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let max = T::MaxAuctionsPerBlock::get();
+            let mut settled: u32 = 0;
+            let mut reads: Weight = 1;
+
+            let mut cursor = AuctionSettlementCursor::<T>::get();
+            if cursor.is_zero() {
+                cursor = now;
+            }
+            while cursor <= now {
+                reads += 1;
+                let budget = max.saturating_sub(settled) as usize;
+                let ending: Vec<T::KittyIndex> = AuctionEndings::<T>::iter_prefix(cursor)
+                    .map(|(kitty_id, _)| kitty_id)
+                    .take(budget.saturating_add(1))
+                    .collect();
+                let finished = ending.len() <= budget;
+
+                for kitty_id in ending.into_iter().take(budget) {
+                    AuctionEndings::<T>::remove(cursor, kitty_id);
+                    Self::settle_auction(kitty_id);
+                    settled += 1;
+                }
+                if !finished {
+                    break;
+                }
+                cursor = cursor.saturating_add(One::one());
+            }
+            AuctionSettlementCursor::<T>::put(cursor);
+
+            T::DbWeight::get()
+                .reads_writes(reads, 1)
+                .saturating_add(T::WeightInfo::settle_auction().saturating_mul(settled.into()))
+        }
+
+        // 使用区块剩余权重清理失效报价并退还质押
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let per_block = T::DbWeight::get().reads(1);
+            let per_offer = T::WeightInfo::expire_offer();
+            let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+            if consumed > remaining_weight {
+                return 0;
+            }
+
+            let mut cursor = OfferCleanupCursor::<T>::get();
+            if cursor.is_zero() {
+                cursor = now;
+            }
+            // 仅清理 expires_at < now 的报价
+            while cursor < now && consumed.saturating_add(per_block) <= remaining_weight {
+                consumed = consumed.saturating_add(per_block);
+                let budget = (remaining_weight.saturating_sub(consumed) / per_offer.max(1)) as usize;
+                let expired: Vec<(T::KittyIndex, T::AccountId)> =
+                    OfferExpiries::<T>::iter_prefix(cursor)
+                        .map(|(key, _)| key)
+                        .take(budget.saturating_add(1))
+                        .collect();
+                let finished = expired.len() <= budget;
+
+                for (kitty_id, bidder) in expired.into_iter().take(budget) {
+                    OfferExpiries::<T>::remove(cursor, (kitty_id, bidder.clone()));
+                    Self::release_offer(kitty_id, &bidder);
+                    Self::deposit_event(Event::OfferExpired(bidder, kitty_id));
+                    consumed = consumed.saturating_add(per_offer);
+                }
+                if !finished {
+                    break;
+                }
+                cursor = cursor.saturating_add(One::one());
+            }
+            OfferCleanupCursor::<T>::put(cursor);
+
+            consumed
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MarketplaceFee::get().deconstruct() + T::CreatorRoyalty::get().deconstruct()
+                    <= Permill::one().deconstruct(),
+                "MarketplaceFee and CreatorRoyalty must not exceed the sale price"
+            );
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            crate::migrations::pre_upgrade::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            crate::migrations::post_upgrade::<T>()
+        }
+    }
 
+    // 调用索引按声明顺序分配，新增调用须追加在末尾，不可插入已有调用之间
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::create())]
         pub fn create(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
             let kitty_id = Self::get_kitty_id()?;
+            Self::ensure_can_own(&who)?;
             let dna = Self::random_value(&who);
 
             // 质押资产
-            T::Currency::reserve(&who, T::KittyReserve::get())
-                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            Self::reserve_deposit(&who, kitty_id)?;
 
-            Kitties::<T>::insert(kitty_id, Some(Kitty(dna)));
-            Owner::<T>::insert(kitty_id, Some(who.clone()));
-            KittiesCount::<T>::put(kitty_id + One::one());
+            Self::mint(&who, kitty_id, dna, None, 0);
 
             Self::deposit_event(Event::KittyCreate(who, kitty_id));
             Ok(())
         }
 
-        #[pallet::weight(0)]
+        // 发起转移请求，需接收方确认后才会质押并更改拥有者
+        #[pallet::weight(T::WeightInfo::transfer())]
         pub fn transfer(
             origin: OriginFor<T>,
             new_owner: T::AccountId,
@@ -125,54 +802,42 @@ pub mod pallet {
                 Some(who.clone()) == Owner::<T>::get(kitty_id),
                 Error::<T>::NotOwner
             );
+            Self::ensure_not_in_auction(kitty_id)?;
+            Self::ensure_can_own(&new_owner)?;
 
-            // 新拥有者质押资产
-            T::Currency::reserve(&new_owner, T::KittyReserve::get())
-                .map_err(|_| Error::<T>::MoneyNotEnough)?;
-            // 解除原质押资产
-            T::Currency::unreserve(&who, T::KittyReserve::get());
-
-            Owner::<T>::insert(kitty_id, Some(new_owner.clone()));
-            Self::deposit_event(Event::KittyTransfer(who, new_owner, kitty_id));
+            Self::offer_transfer(who, new_owner, kitty_id);
             Ok(())
         }
 
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::bread())]
         pub fn bread(
             origin: OriginFor<T>,
             kitty_id_1: T::KittyIndex,
             kitty_id_2: T::KittyIndex,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let kitty_id = Self::get_kitty_id()?;
-
-            ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameParentIndex);
-
-            let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyIndex)?;
-            let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyIndex)?;
-            let dna_1 = kitty1.0;
-            let dna_2 = kitty2.0;
+            let (kitty1, kitty2) = Self::breeding_parents(&who, kitty_id_1, kitty_id_2)?;
 
-            let selector = Self::random_value(&who);
-            let mut new_dna = [0u8; 16];
-
-            for i in 0..dna_1.len() {
-                new_dna[i] = (selector[i] & dna_1[i]) | (!selector[i] & dna_2[i]);
+            // 父方(sire)为本人所有或已获授权
+            let sire_approved = Some(who.clone()) != Owner::<T>::get(kitty_id_2);
+            if sire_approved {
+                ensure!(
+                    Some(who.clone()) == SireApprovals::<T>::get(kitty_id_2),
+                    Error::<T>::NotSireApproved
+                );
             }
 
-            // 质押资产
-            T::Currency::reserve(&who, T::KittyReserve::get())
-                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            Self::do_breed(&who, (kitty_id_1, kitty1), (kitty_id_2, kitty2))?;
 
-            Kitties::<T>::insert(kitty_id, Some(Kitty(new_dna)));
-            Owner::<T>::insert(kitty_id, Some(who.clone()));
-            KittiesCount::<T>::put(kitty_id + One::one());
-            Self::deposit_event(Event::KittyCreate(who, kitty_id));
+            // 授权仅可使用一次
+            if sire_approved {
+                SireApprovals::<T>::remove(kitty_id_2);
+            }
 
             Ok(())
         }
 
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::sale())]
         pub fn sale(
             origin: OriginFor<T>,
             kitty_id: T::KittyIndex,
@@ -184,65 +849,1300 @@ pub mod pallet {
                 Error::<T>::NotOwner
             );
 
-            KittyPrices::<T>::insert(kitty_id, sale_price);
-
-            Self::deposit_event(Event::KittyForSale(who, kitty_id, sale_price));
+            match sale_price {
+                Some(price) => {
+                    Self::ensure_not_in_auction(kitty_id)?;
+                    KittyPrices::<T>::insert(kitty_id, price);
+                    DutchAuctions::<T>::remove(kitty_id);
+                    KittyPriceAssets::<T>::remove(kitty_id);
+                    Self::deposit_event(Event::KittyForSale(who, kitty_id, price));
+                }
+                // 取消挂售
+                None => {
+                    ensure!(
+                        KittyPrices::<T>::get(kitty_id).is_some(),
+                        Error::<T>::NotForSale
+                    );
+                    Self::cancel_sale(&who, kitty_id);
+                }
+            }
             Ok(())
         }
 
-        #[pallet::weight(0)]
+        #[pallet::weight(T::WeightInfo::buy().max(T::WeightInfo::buy_in_asset()))]
+        #[transactional]
         pub fn buy(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
             let who = ensure_signed(origin)?;
             let kitty_owner = Owner::<T>::get(kitty_id).ok_or(Error::<T>::NotOwner)?;
-            let kitty_price = KittyPrices::<T>::get(kitty_id).ok_or(Error::<T>::NotForSale)?;
+            let (kitty_price, price_asset) =
+                Self::current_price(kitty_id).ok_or(Error::<T>::NotForSale)?;
             ensure!(
                 Some(who.clone()) != Some(kitty_owner.clone()),
                 Error::<T>::AlreadyOwned
             );
+            Self::ensure_can_own(&who)?;
 
-            // 新拥有者质押资产
-            T::Currency::reserve(&who, T::KittyReserve::get())
-                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            // 新拥有者质押资产，解除原质押资产
+            Self::transfer_deposit(&kitty_owner, &who, kitty_id)?;
 
-            //转账（购买）
-            T::Currency::transfer(
-                &who,
-                &kitty_owner,
-                kitty_price,
-                ExistenceRequirement::KeepAlive,
-            )?;
+            //转账（购买），扣除市场手续费及创建者版税
+            let (fee, royalty) = match price_asset {
+                Some(asset_id) => {
+                    Self::pay_sale_in_asset(&who, &kitty_owner, kitty_id, asset_id, kitty_price)?
+                }
+                None => Self::pay_sale(&who, &kitty_owner, kitty_id, kitty_price)?,
+            };
 
-            // 解除原质押资产
-            T::Currency::unreserve(&kitty_owner, T::KittyReserve::get());
+            //移除挂售
+            KittyPrices::<T>::remove(kitty_id);
+            DutchAuctions::<T>::remove(kitty_id);
+            KittyPriceAssets::<T>::remove(kitty_id);
 
             //更改拥有人
-            Owner::<T>::insert(kitty_id, Some(who.clone()));
+            Self::change_owner(&kitty_owner, &who, kitty_id);
 
-            //移除挂售
-            KittyPrices::<T>::remove(kitty_id);
+            Self::deposit_event(Event::KittySaleOut(who, kitty_id, kitty_price, fee, royalty));
+            Ok(())
+        }
+
+        // 接收方确认转移
+        #[pallet::weight(T::WeightInfo::accept_transfer())]
+        pub fn accept_transfer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let pending = PendingTransfers::<T>::get(kitty_id).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(who == pending.to, Error::<T>::NotTransferRecipient);
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= pending.expires_at,
+                Error::<T>::TransferExpired
+            );
+            ensure!(
+                Some(pending.from.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_can_own(&who)?;
 
-            Self::deposit_event(Event::KittySaleOut(who, kitty_id, Some(kitty_price)));
+            // 新拥有者质押资产，解除原质押资产
+            Self::transfer_deposit(&pending.from, &who, kitty_id)?;
+
+            Self::change_owner(&pending.from, &who, kitty_id);
+            Self::deposit_event(Event::KittyTransfer(pending.from, who, kitty_id));
             Ok(())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        // 获取当前Kitty_id (从0开始)
-        fn get_kitty_id() -> sp_std::result::Result<T::KittyIndex, DispatchError> {
-            let kitty_id = Self::kitties_count();
-            if kitty_id == T::KittyIndex::max_value() {
-                return Err(Error::<T>::KittiesCountOverflow.into());
-            }
-            Ok(kitty_id)
+        // 接收方拒绝转移
+        #[pallet::weight(T::WeightInfo::reject_transfer())]
+        pub fn reject_transfer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let pending = PendingTransfers::<T>::get(kitty_id).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(who == pending.to, Error::<T>::NotTransferRecipient);
+
+            PendingTransfers::<T>::remove(kitty_id);
+            Self::deposit_event(Event::KittyTransferRejected(who, kitty_id));
+            Ok(())
         }
 
-        fn random_value(sender: &T::AccountId) -> [u8; 16] {
-            let payload = (
-                T::Randomness::random_seed(), // 通过最近区块信息生成的随机数种子
-                &sender,
-                <frame_system::Pallet<T>>::extrinsic_index(), // 当前交易在区块中的顺序
+        // 发起方取消转移
+        #[pallet::weight(T::WeightInfo::cancel_transfer())]
+        pub fn cancel_transfer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let pending = PendingTransfers::<T>::get(kitty_id).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(who == pending.from, Error::<T>::NotOwner);
+
+            PendingTransfers::<T>::remove(kitty_id);
+            Self::deposit_event(Event::KittyTransferCancelled(who, kitty_id));
+            Ok(())
+        }
+
+        // 授权他人使用本人的Kitty作为父方(sire)繁殖
+        #[pallet::weight(T::WeightInfo::approve_siring())]
+        pub fn approve_siring(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            ensure!(
+                Some(owner.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
             );
-            payload.using_encoded(blake2_128)
+
+            SireApprovals::<T>::insert(kitty_id, who.clone());
+
+            Self::deposit_event(Event::SiringApproved(owner, kitty_id, who));
+            Ok(())
+        }
+
+        // 撤销繁殖授权
+        #[pallet::weight(T::WeightInfo::revoke_siring())]
+        pub fn revoke_siring(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            ensure!(
+                Some(owner.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            ensure!(
+                SireApprovals::<T>::contains_key(kitty_id),
+                Error::<T>::NotSireApproved
+            );
+
+            SireApprovals::<T>::remove(kitty_id);
+
+            Self::deposit_event(Event::SiringRevoked(owner, kitty_id));
+            Ok(())
+        }
+
+        // 挂出父方(sire)配种服务，None 为取消
+        #[pallet::weight(T::WeightInfo::list_sire())]
+        pub fn list_sire(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            siring_price: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+
+            match siring_price {
+                Some(price) => {
+                    SirePrices::<T>::insert(kitty_id, price);
+                    Self::deposit_event(Event::SireForSale(who, kitty_id, price));
+                }
+                None => {
+                    ensure!(
+                        SirePrices::<T>::contains_key(kitty_id),
+                        Error::<T>::SireNotListed
+                    );
+                    SirePrices::<T>::remove(kitty_id);
+                    Self::deposit_event(Event::SireListingCancelled(who, kitty_id));
+                }
+            }
+            Ok(())
+        }
+
+        // 支付配种费用，使用挂出的父方与本人的母方繁殖，幼崽归本人所有
+        #[pallet::weight(T::WeightInfo::buy_siring())]
+        #[transactional]
+        pub fn buy_siring(
+            origin: OriginFor<T>,
+            matron_id: T::KittyIndex,
+            sire_id: T::KittyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (matron, sire) = Self::breeding_parents(&who, matron_id, sire_id)?;
+            let siring_price = SirePrices::<T>::get(sire_id).ok_or(Error::<T>::SireNotListed)?;
+            let sire_owner = Owner::<T>::get(sire_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(who != sire_owner, Error::<T>::AlreadyOwned);
+
+            // 向父方拥有者支付配种费用
+            T::Currency::transfer(
+                &who,
+                &sire_owner,
+                siring_price,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            Self::do_breed(&who, (matron_id, matron), (sire_id, sire))?;
+
+            Self::deposit_event(Event::SiringPurchased(who, matron_id, sire_id, siring_price));
+            Ok(())
+        }
+
+        // 销毁Kitty并解除质押，清除元数据，退还所有报价，血统信息保留为墓碑。
+        // offers 为该Kitty报价数量的上限，用于预估权重
+        #[pallet::weight(T::WeightInfo::burn(*offers))]
+        pub fn burn(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            offers: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_not_in_auction(kitty_id)?;
+            ensure!(
+                Offers::<T>::iter_prefix(kitty_id).count() as u32 <= offers,
+                Error::<T>::OfferWitnessTooLow
+            );
+            let kitty = Kitties::<T>::take(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+            Owner::<T>::remove(kitty_id);
+            Self::remove_owned_kitty(&who, kitty_id);
+            Self::cancel_sale(&who, kitty_id);
+            PendingTransfers::<T>::remove(kitty_id);
+            SireApprovals::<T>::remove(kitty_id);
+            if SirePrices::<T>::take(kitty_id).is_some() {
+                Self::deposit_event(Event::SireListingCancelled(who.clone(), kitty_id));
+            }
+            if Approvals::<T>::take(kitty_id).is_some() {
+                Self::deposit_event(Event::KittyApprovalCleared(who.clone(), kitty_id));
+            }
+            Cooldowns::<T>::remove(kitty_id);
+            if Self::remove_metadata(kitty_id) {
+                Self::deposit_event(Event::MetadataCleared(who.clone(), kitty_id));
+            }
+            for (bidder, offer) in Offers::<T>::drain_prefix(kitty_id) {
+                OfferExpiries::<T>::remove(offer.expires_at, (kitty_id, bidder.clone()));
+                T::Currency::unreserve_named(
+                    &RESERVE_ID,
+                    &bidder,
+                    offer.amount.saturating_add(offer.deposit),
+                );
+                Self::deposit_event(Event::OfferCancelled(bidder, kitty_id));
+            }
+
+            Tombstones::<T>::insert(
+                kitty_id,
+                Tombstone {
+                    kitty,
+                    owner: who.clone(),
+                    burned_at: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            // 解除质押资产
+            Self::release_deposit(&who, kitty_id);
+
+            Self::deposit_event(Event::KittyBurned(who, kitty_id));
+            Ok(())
+        }
+
+        // 授权他人转移Kitty，None 为清除授权，拥有者或其操作者可调用
+        #[pallet::weight(T::WeightInfo::approve())]
+        pub fn approve(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            spender: Option<T::AccountId>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let owner = Owner::<T>::get(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(
+                who == owner || Self::is_operator(&owner, &who),
+                Error::<T>::NotOwner
+            );
+
+            match spender {
+                Some(spender) => {
+                    ensure!(spender != owner, Error::<T>::AlreadyOwned);
+                    Approvals::<T>::insert(kitty_id, spender.clone());
+                    Self::deposit_event(Event::KittyApproved(owner, spender, kitty_id));
+                }
+                None => {
+                    ensure!(
+                        Approvals::<T>::take(kitty_id).is_some(),
+                        Error::<T>::NotApproved
+                    );
+                    Self::deposit_event(Event::KittyApprovalCleared(owner, kitty_id));
+                }
+            }
+            Ok(())
+        }
+
+        // 授权或撤销操作者转移本人全部Kitty
+        #[pallet::weight(T::WeightInfo::set_approval_for_all())]
+        pub fn set_approval_for_all(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            approved: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(who != operator, Error::<T>::AlreadyOwned);
+
+            if approved {
+                OperatorApprovals::<T>::insert(&who, &operator, ());
+            } else {
+                OperatorApprovals::<T>::remove(&who, &operator);
+            }
+
+            Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
+            Ok(())
+        }
+
+        // 由拥有者、被授权账户或操作者转移Kitty。转给调用者本人时直接转移并由其质押，
+        // 转给他人时与 transfer 相同，需接收方确认后才会质押并更改拥有者
+        #[pallet::weight(T::WeightInfo::transfer_from())]
+        pub fn transfer_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            kitty_id: T::KittyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(from.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            ensure!(
+                who == from
+                    || Some(who.clone()) == Approvals::<T>::get(kitty_id)
+                    || Self::is_operator(&from, &who),
+                Error::<T>::NotApproved
+            );
+            ensure!(from != to, Error::<T>::AlreadyOwned);
+            Self::ensure_not_in_auction(kitty_id)?;
+            Self::ensure_can_own(&to)?;
+
+            if to != who {
+                Self::offer_transfer(from, to, kitty_id);
+                return Ok(());
+            }
+
+            // 新拥有者质押资产，解除原质押资产
+            Self::transfer_deposit(&from, &to, kitty_id)?;
+
+            Self::change_owner(&from, &to, kitty_id);
+            Self::deposit_event(Event::KittyTransfer(from, to, kitty_id));
+            Ok(())
+        }
+
+        // 发起英式拍卖，挂售及未确认的转移请求将被取消
+        #[pallet::weight(T::WeightInfo::create_auction())]
+        pub fn create_auction(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            reserve_price: BalanceOf<T>,
+            duration: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_not_in_auction(kitty_id)?;
+            ensure!(
+                !duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+                Error::<T>::InvalidAuctionDuration
+            );
+
+            Self::cancel_sale(&who, kitty_id);
+            PendingTransfers::<T>::remove(kitty_id);
+
+            let end_block = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+            Auctions::<T>::insert(
+                kitty_id,
+                Auction {
+                    seller: who.clone(),
+                    reserve_price,
+                    end_block,
+                    highest_bid: None,
+                    bid_deposit: Zero::zero(),
+                },
+            );
+            AuctionEndings::<T>::insert(end_block, kitty_id, ());
+
+            Self::deposit_event(Event::AuctionCreated(who, kitty_id, reserve_price, end_block));
+            Ok(())
+        }
+
+        // 出价，质押出价金额及Kitty质押，被超过时退还
+        #[pallet::weight(T::WeightInfo::bid())]
+        pub fn bid(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut auction = Auctions::<T>::get(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(now < auction.end_block, Error::<T>::AuctionEnded);
+            ensure!(who != auction.seller, Error::<T>::AlreadyOwned);
+            Self::ensure_can_own(&who)?;
+            ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+            if let Some((_, highest)) = &auction.highest_bid {
+                ensure!(amount > *highest, Error::<T>::BidTooLow);
+            }
+
+            // 质押出价及成交后的Kitty质押
+            let deposit = Self::kitty_reserve();
+            T::Currency::reserve_named(&RESERVE_ID, &who, amount.saturating_add(deposit))
+                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            // 退还被超过的出价
+            if let Some((bidder, highest)) = auction.highest_bid.take() {
+                T::Currency::unreserve_named(
+                    &RESERVE_ID,
+                    &bidder,
+                    highest.saturating_add(auction.bid_deposit),
+                );
+            }
+            auction.highest_bid = Some((who.clone(), amount));
+            auction.bid_deposit = deposit;
+
+            // 防狙击：临近结束的出价延长拍卖
+            let extended_end = now.saturating_add(T::AuctionExtension::get());
+            let extended = extended_end > auction.end_block;
+            if extended {
+                AuctionEndings::<T>::remove(auction.end_block, kitty_id);
+                AuctionEndings::<T>::insert(extended_end, kitty_id, ());
+                auction.end_block = extended_end;
+            }
+            Auctions::<T>::insert(kitty_id, auction);
+
+            Self::deposit_event(Event::BidPlaced(who, kitty_id, amount));
+            if extended {
+                Self::deposit_event(Event::AuctionExtended(kitty_id, extended_end));
+            }
+            Ok(())
+        }
+
+        // 发起荷兰式拍卖，以挂售方式出售，价格随区块下降，到期后保持结束价
+        #[pallet::weight(T::WeightInfo::create_dutch_auction())]
+        pub fn create_dutch_auction(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            start_price: BalanceOf<T>,
+            end_price: BalanceOf<T>,
+            duration: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_not_in_auction(kitty_id)?;
+            ensure!(start_price >= end_price, Error::<T>::InvalidPriceRange);
+            ensure!(
+                !duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+                Error::<T>::InvalidAuctionDuration
+            );
+
+            let start_block = <frame_system::Pallet<T>>::block_number();
+            let end_block = start_block.saturating_add(duration);
+            KittyPrices::<T>::insert(kitty_id, start_price);
+            KittyPriceAssets::<T>::remove(kitty_id);
+            DutchAuctions::<T>::insert(
+                kitty_id,
+                DutchAuction {
+                    start_price,
+                    end_price,
+                    start_block,
+                    end_block,
+                },
+            );
+
+            Self::deposit_event(Event::DutchAuctionCreated(
+                who,
+                kitty_id,
+                start_price,
+                end_price,
+                end_block,
+            ));
+            Ok(())
+        }
+
+        // 对任意Kitty报价，报价金额及Kitty质押被质押，同一买方的新报价替换旧报价
+        #[pallet::weight(T::WeightInfo::make_offer())]
+        #[transactional]
+        pub fn make_offer(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            amount: BalanceOf<T>,
+            expiry: T::BlockNumber,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let owner = Owner::<T>::get(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            ensure!(who != owner, Error::<T>::AlreadyOwned);
+            Self::ensure_can_own(&who)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                expiry >= now && expiry <= now.saturating_add(T::MaxOfferDuration::get()),
+                Error::<T>::InvalidOfferExpiry
+            );
+
+            // 替换旧报价，新报价质押失败时整个调用回滚，旧报价保留
+            if let Some(offer) = Offers::<T>::get(kitty_id, &who) {
+                OfferExpiries::<T>::remove(offer.expires_at, (kitty_id, who.clone()));
+                Self::release_offer(kitty_id, &who);
+            }
+
+            let deposit = Self::kitty_reserve();
+            T::Currency::reserve_named(&RESERVE_ID, &who, amount.saturating_add(deposit))
+                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            Offers::<T>::insert(
+                kitty_id,
+                &who,
+                Offer {
+                    amount,
+                    expires_at: expiry,
+                    deposit,
+                },
+            );
+            OfferExpiries::<T>::insert(expiry, (kitty_id, who.clone()), ());
+
+            Self::deposit_event(Event::OfferMade(who, kitty_id, amount, expiry));
+            Ok(())
+        }
+
+        // 拥有者接受报价，报价金额支付给拥有者并转移Kitty
+        #[pallet::weight(T::WeightInfo::accept_offer())]
+        #[transactional]
+        pub fn accept_offer(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            bidder: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_not_in_auction(kitty_id)?;
+            let offer = Offers::<T>::get(kitty_id, &bidder).ok_or(Error::<T>::NoOffer)?;
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= offer.expires_at,
+                Error::<T>::OfferExpired
+            );
+            Self::ensure_can_own(&bidder)?;
+
+            Offers::<T>::remove(kitty_id, &bidder);
+            OfferExpiries::<T>::remove(offer.expires_at, (kitty_id, bidder.clone()));
+
+            // 报价时已质押，扣除市场手续费及创建者版税，Kitty质押保留给新拥有者
+            let (fee, royalty) =
+                Self::pay_sale_from_reserved(&bidder, &who, kitty_id, offer.amount)?;
+            Self::release_deposit(&who, kitty_id);
+            KittyDeposits::<T>::insert(kitty_id, offer.deposit);
+            Self::change_owner(&who, &bidder, kitty_id);
+
+            Self::deposit_event(Event::OfferAccepted(
+                who,
+                bidder,
+                kitty_id,
+                offer.amount,
+                fee,
+                royalty,
+            ));
+            Ok(())
+        }
+
+        // 买方撤回报价并解除质押
+        #[pallet::weight(T::WeightInfo::cancel_offer())]
+        pub fn cancel_offer(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let offer = Offers::<T>::get(kitty_id, &who).ok_or(Error::<T>::NoOffer)?;
+
+            OfferExpiries::<T>::remove(offer.expires_at, (kitty_id, who.clone()));
+            Self::release_offer(kitty_id, &who);
+
+            Self::deposit_event(Event::OfferCancelled(who, kitty_id));
+            Ok(())
+        }
+
+        // 以资产挂售，价格以该资产的最小单位计
+        #[pallet::weight(T::WeightInfo::sale_in_asset())]
+        pub fn sale_in_asset(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            asset_id: AssetIdOf<T>,
+            price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::ensure_not_in_auction(kitty_id)?;
+            Self::to_asset_balance(price)?;
+
+            KittyPrices::<T>::insert(kitty_id, price);
+            DutchAuctions::<T>::remove(kitty_id);
+            KittyPriceAssets::<T>::insert(kitty_id, asset_id);
+
+            Self::deposit_event(Event::KittyForSaleInAsset(who, kitty_id, asset_id, price));
+            Ok(())
+        }
+
+        // 设置Kitty质押数量，仅影响之后的质押，已有Kitty按记录解除质押
+        #[pallet::weight(T::WeightInfo::set_kitty_reserve())]
+        pub fn set_kitty_reserve(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+
+            KittyReserveAmount::<T>::put(amount);
+
+            Self::deposit_event(Event::KittyReserveSet(amount));
+            Ok(())
+        }
+
+        // 设置市场手续费比例，与创建者版税之和不超过 100%
+        #[pallet::weight(T::WeightInfo::set_marketplace_fee())]
+        pub fn set_marketplace_fee(origin: OriginFor<T>, fee: Permill) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            ensure!(
+                fee.deconstruct() + Self::creator_royalty().deconstruct()
+                    <= Permill::one().deconstruct(),
+                Error::<T>::InvalidFeeRate
+            );
+
+            MarketplaceFeeRate::<T>::put(fee);
+
+            Self::deposit_event(Event::MarketplaceFeeSet(fee));
+            Ok(())
+        }
+
+        // 设置创建者版税比例，与市场手续费之和不超过 100%
+        #[pallet::weight(T::WeightInfo::set_creator_royalty())]
+        pub fn set_creator_royalty(origin: OriginFor<T>, royalty: Permill) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            ensure!(
+                Self::marketplace_fee().deconstruct() + royalty.deconstruct()
+                    <= Permill::one().deconstruct(),
+                Error::<T>::InvalidFeeRate
+            );
+
+            CreatorRoyaltyRate::<T>::put(royalty);
+
+            Self::deposit_event(Event::CreatorRoyaltySet(royalty));
+            Ok(())
+        }
+
+        // 拥有者设置名称及元数据URI，按字节数质押，重新设置时补足或退还差额
+        #[pallet::weight(T::WeightInfo::set_metadata(name.len() as u32, uri.len() as u32))]
+        pub fn set_metadata(
+            origin: OriginFor<T>,
+            kitty_id: T::KittyIndex,
+            name: Vec<u8>,
+            uri: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let limit = T::StringLimit::get() as usize;
+            ensure!(
+                name.len() <= limit && uri.len() <= limit,
+                Error::<T>::BadMetadata
+            );
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+
+            let bytes = (name.len() + uri.len()) as u32;
+            let deposit = T::MetadataDepositPerByte::get()
+                .saturating_mul(bytes.into())
+                .saturating_add(T::MetadataDepositBase::get());
+
+            Metadata::<T>::try_mutate(kitty_id, |metadata| -> DispatchResult {
+                match metadata.take() {
+                    Some(old) if old.depositor == who => {
+                        if deposit > old.deposit {
+                            T::Currency::reserve_named(
+                                &RESERVE_ID,
+                                &who,
+                                deposit.saturating_sub(old.deposit),
+                            )
+                            .map_err(|_| Error::<T>::MoneyNotEnough)?;
+                        } else {
+                            T::Currency::unreserve_named(
+                                &RESERVE_ID,
+                                &who,
+                                old.deposit.saturating_sub(deposit),
+                            );
+                        }
+                    }
+                    // 原质押属于之前的拥有者，退还给该账户
+                    Some(old) => {
+                        T::Currency::reserve_named(&RESERVE_ID, &who, deposit)
+                            .map_err(|_| Error::<T>::MoneyNotEnough)?;
+                        T::Currency::unreserve_named(&RESERVE_ID, &old.depositor, old.deposit);
+                    }
+                    None => {
+                        T::Currency::reserve_named(&RESERVE_ID, &who, deposit)
+                            .map_err(|_| Error::<T>::MoneyNotEnough)?;
+                    }
+                }
+                *metadata = Some(KittyMetadata {
+                    depositor: who.clone(),
+                    deposit,
+                    name: name.clone(),
+                    uri: uri.clone(),
+                });
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::MetadataSet(who, kitty_id, name, uri));
+            Ok(())
+        }
+
+        // 拥有者或设置者清除名称及元数据URI，质押退还给设置者。
+        // 拥有者变更后，原设置者仍可清除以取回质押
+        #[pallet::weight(T::WeightInfo::clear_metadata())]
+        pub fn clear_metadata(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let metadata = Metadata::<T>::get(kitty_id).ok_or(Error::<T>::NoMetadata)?;
+            ensure!(
+                who == metadata.depositor || Some(who.clone()) == Owner::<T>::get(kitty_id),
+                Error::<T>::NotOwner
+            );
+            Self::remove_metadata(kitty_id);
+
+            Self::deposit_event(Event::MetadataCleared(who, kitty_id));
+            Ok(())
+        }
+
+        // 设置每个账户最多拥有的Kitty数量，不超过 T::MaxKittiesPerAccount 以限定拥有索引的大小。
+        // 已超出上限的账户保留现有Kitty，但不能再获得新的Kitty
+        #[pallet::weight(T::WeightInfo::set_max_kitties_per_account())]
+        pub fn set_max_kitties_per_account(origin: OriginFor<T>, max: u32) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            ensure!(max <= T::MaxKittiesPerAccount::get(), Error::<T>::MaxKittiesTooHigh);
+
+            MaxKittiesPerAccountLimit::<T>::put(max);
+
+            Self::deposit_event(Event::MaxKittiesPerAccountSet(max));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // 获取当前Kitty_id (从0开始)
+        fn get_kitty_id() -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+            let kitty_id = Self::kitties_count();
+            if kitty_id == T::KittyIndex::max_value() {
+                return Err(Error::<T>::KittiesCountOverflow.into());
+            }
+            Ok(kitty_id)
+        }
+
+        // 写入新Kitty并记录拥有者，创建者即首个拥有者
+        fn mint(
+            owner: &T::AccountId,
+            kitty_id: T::KittyIndex,
+            dna: [u8; 16],
+            parents: Option<(T::KittyIndex, T::KittyIndex)>,
+            generation: u32,
+        ) {
+            let kitty = Kitty {
+                dna,
+                parents,
+                generation,
+                birth_block: <frame_system::Pallet<T>>::block_number(),
+                creator: owner.clone(),
+            };
+            Kitties::<T>::insert(kitty_id, kitty);
+            Owner::<T>::insert(kitty_id, owner.clone());
+            Self::add_owned_kitty(owner, kitty_id);
+            KittiesCount::<T>::put(kitty_id + One::one());
+        }
+
+        fn random_value(sender: &T::AccountId) -> [u8; 16] {
+            let payload = (
+                T::Randomness::random_seed(), // 通过最近区块信息生成的随机数种子
+                &sender,
+                <frame_system::Pallet<T>>::extrinsic_index(), // 当前交易在区块中的顺序
+            );
+            payload.using_encoded(blake2_128)
+        }
+
+        // 获取账户拥有的所有Kitty (按kitty_id升序)
+        pub fn kitties_of(owner: &T::AccountId) -> Vec<T::KittyIndex> {
+            let mut kitties: Vec<T::KittyIndex> =
+                OwnedKitties::<T>::iter_prefix(owner).map(|(kitty_id, _)| kitty_id).collect();
+            kitties.sort();
+            kitties
+        }
+
+        // 查询Kitty信息，已销毁的Kitty返回墓碑中的血统信息
+        pub fn kitty_info(kitty_id: T::KittyIndex) -> Option<KittyInfoOf<T>> {
+            let (kitty, owner, burned_at) = match Self::kitties(kitty_id) {
+                Some(kitty) => (kitty, Self::owner(kitty_id)?, None),
+                None => {
+                    let tombstone = Self::tombstones(kitty_id)?;
+                    (tombstone.kitty, tombstone.owner, Some(tombstone.burned_at))
+                }
+            };
+            let (price, price_asset) = match Self::current_price(kitty_id) {
+                Some((price, price_asset)) => (Some(price), price_asset),
+                None => (None, None),
+            };
+            Some(KittyInfo {
+                dna: kitty.dna,
+                owner,
+                price,
+                price_asset,
+                parents: kitty.parents,
+                generation: kitty.generation,
+                birth_block: kitty.birth_block,
+                creator: kitty.creator,
+                burned_at,
+            })
+        }
+
+        // 分页查询账户拥有的Kitty，返回start_after之后的最多limit个
+        pub fn kitties_of_owner(
+            owner: &T::AccountId,
+            start_after: Option<T::KittyIndex>,
+            limit: u32,
+        ) -> Vec<T::KittyIndex> {
+            Self::kitties_of(owner)
+                .into_iter()
+                .filter(|kitty_id| start_after.map_or(true, |start| *kitty_id > start))
+                .take(limit as usize)
+                .collect()
+        }
+
+        // 分页查询挂售中的Kitty、价格及计价资产，返回start_after之后的最多limit个
+        pub fn kitties_for_sale(
+            start_after: Option<T::KittyIndex>,
+            limit: u32,
+        ) -> Vec<(T::KittyIndex, BalanceOf<T>, Option<AssetIdOf<T>>)> {
+            let mut sales: Vec<(T::KittyIndex, BalanceOf<T>, Option<AssetIdOf<T>>)> =
+                KittyPrices::<T>::iter()
+                    .map(|(kitty_id, price)| {
+                        (
+                            kitty_id,
+                            Self::dutch_price(kitty_id).unwrap_or(price),
+                            Self::kitty_price_assets(kitty_id),
+                        )
+                    })
+                    .filter(|(kitty_id, _, _)| start_after.map_or(true, |start| *kitty_id > start))
+                    .collect();
+            sales.sort_by_key(|(kitty_id, _, _)| *kitty_id);
+            sales.truncate(limit as usize);
+            sales
+        }
+
+        // 查询挂售中Kitty的当前价格及计价资产，荷兰式拍卖按当前区块计算，资产为 None 时以 T::Currency 计价
+        pub fn current_price(
+            kitty_id: T::KittyIndex,
+        ) -> Option<(BalanceOf<T>, Option<AssetIdOf<T>>)> {
+            let price = Self::kitty_prices(kitty_id)?;
+            Some((
+                Self::dutch_price(kitty_id).unwrap_or(price),
+                Self::kitty_price_assets(kitty_id),
+            ))
+        }
+
+        // 荷兰式拍卖当前价格 = 起始价 - (起始价 - 结束价) * 已过区块数 / 持续区块数
+        fn dutch_price(kitty_id: T::KittyIndex) -> Option<BalanceOf<T>> {
+            let auction = Self::dutch_auctions(kitty_id)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            if now >= auction.end_block {
+                return Some(auction.end_price);
+            }
+
+            let price_drop: u128 =
+                auction.start_price.saturating_sub(auction.end_price).saturated_into();
+            let elapsed: u128 = now.saturating_sub(auction.start_block).saturated_into();
+            let duration: u128 =
+                auction.end_block.saturating_sub(auction.start_block).saturated_into();
+            let discount =
+                multiply_by_rational(price_drop, elapsed, duration).unwrap_or(price_drop);
+            Some(auction.start_price.saturating_sub(discount.saturated_into()))
+        }
+
+        // 校验父母索引及母方(matron)归属，返回父母
+        fn breeding_parents(
+            who: &T::AccountId,
+            matron_id: T::KittyIndex,
+            sire_id: T::KittyIndex,
+        ) -> sp_std::result::Result<(KittyOf<T>, KittyOf<T>), DispatchError> {
+            ensure!(matron_id != sire_id, Error::<T>::SameParentIndex);
+
+            let matron = Self::kitties(matron_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+            let sire = Self::kitties(sire_id).ok_or(Error::<T>::InvalidKittyIndex)?;
+
+            // 母方(matron)必须为本人所有
+            ensure!(
+                Some(who.clone()) == Owner::<T>::get(matron_id),
+                Error::<T>::NotOwner
+            );
+            Ok((matron, sire))
+        }
+
+        // 繁殖幼崽，幼崽归 who 所有并由 who 质押
+        fn do_breed(
+            who: &T::AccountId,
+            (kitty_id_1, kitty1): (T::KittyIndex, KittyOf<T>),
+            (kitty_id_2, kitty2): (T::KittyIndex, KittyOf<T>),
+        ) -> sp_std::result::Result<T::KittyIndex, DispatchError> {
+            let kitty_id = Self::get_kitty_id()?;
+            Self::ensure_can_own(who)?;
+
+            // 父母均需度过冷却期
+            let now = <frame_system::Pallet<T>>::block_number();
+            let cooldown_1 = Cooldowns::<T>::get(kitty_id_1);
+            let cooldown_2 = Cooldowns::<T>::get(kitty_id_2);
+            ensure!(
+                cooldown_1.ready_at <= now && cooldown_2.ready_at <= now,
+                Error::<T>::KittyOnCooldown
+            );
+
+            // 父母需为一雄一雌
+            ensure!(
+                kitty1.gender() != kitty2.gender(),
+                Error::<T>::IncompatibleGender
+            );
+
+            let generation = kitty1.generation.max(kitty2.generation).saturating_add(1);
+
+            // 按显性/隐性基因遗传，同一随机种子得到相同的幼崽
+            let seed = Self::random_value(who);
+            let new_dna = kitty_genetics::breed(&kitty1.dna, &kitty2.dna, &seed);
+
+            // 质押资产
+            Self::reserve_deposit(who, kitty_id)?;
+
+            Self::mint(who, kitty_id, new_dna, Some((kitty_id_1, kitty_id_2)), generation);
+
+            // 更新父母冷却，幼崽按代数进入首次冷却
+            Self::start_cooldown(kitty_id_1, cooldown_1.breed_count, kitty1.generation, now);
+            Self::start_cooldown(kitty_id_2, cooldown_2.breed_count, kitty2.generation, now);
+            Cooldowns::<T>::insert(
+                kitty_id,
+                BreedingCooldown {
+                    breed_count: 0,
+                    ready_at: now.saturating_add(Self::cooldown_period(0, generation)),
+                },
+            );
+
+            Self::deposit_event(Event::KittyCreate(who.clone(), kitty_id));
+            Ok(kitty_id)
+        }
+
+        // 查询Kitty血统记录，已销毁的Kitty返回墓碑中的记录
+        pub fn lineage_of(kitty_id: T::KittyIndex) -> Option<KittyOf<T>> {
+            Self::kitties(kitty_id).or_else(|| Self::tombstones(kitty_id).map(|t| t.kitty))
+        }
+
+        // 查询Kitty性别
+        pub fn gender_of(kitty_id: T::KittyIndex) -> Option<Gender> {
+            Self::kitties(kitty_id).map(|kitty| kitty.gender())
+        }
+
+        // 查询由DNA解码的外观特征
+        pub fn traits_of(kitty_id: T::KittyIndex) -> Option<Traits> {
+            Self::kitties(kitty_id).map(|kitty| kitty.traits())
+        }
+
+        // 查询Kitty可再次繁殖的区块
+        pub fn breeding_ready_at(kitty_id: T::KittyIndex) -> Option<T::BlockNumber> {
+            Self::kitties(kitty_id)?;
+            Some(Cooldowns::<T>::get(kitty_id).ready_at)
+        }
+
+        // 冷却区块数 = BaseCooldown * 2^(繁殖次数 + 代数 / 2)，不超过 MaxCooldown
+        pub fn cooldown_period(breed_count: u32, generation: u32) -> T::BlockNumber {
+            let exponent = breed_count.saturating_add(generation / 2).min(16);
+            T::BaseCooldown::get()
+                .saturating_mul((1u32 << exponent).into())
+                .min(T::MaxCooldown::get())
+        }
+
+        // 记录一次繁殖并开始冷却
+        fn start_cooldown(
+            kitty_id: T::KittyIndex,
+            breed_count: u32,
+            generation: u32,
+            now: T::BlockNumber,
+        ) {
+            let ready_at = now.saturating_add(Self::cooldown_period(breed_count, generation));
+            Cooldowns::<T>::insert(
+                kitty_id,
+                BreedingCooldown {
+                    breed_count: breed_count.saturating_add(1),
+                    ready_at,
+                },
+            );
+        }
+
+        // 判断账户是否拥有指定Kitty
+        pub fn is_owned_by(owner: &T::AccountId, kitty_id: T::KittyIndex) -> bool {
+            OwnedKitties::<T>::contains_key(owner, kitty_id)
+        }
+
+        // 拥有数量未达上限时才可获得新的Kitty
+        fn ensure_can_own(who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                OwnedKittiesCount::<T>::get(who) < Self::max_kitties_per_account(),
+                Error::<T>::TooManyKitties
+            );
+            Ok(())
+        }
+
+        fn ensure_not_in_auction(kitty_id: T::KittyIndex) -> DispatchResult {
+            ensure!(
+                !Auctions::<T>::contains_key(kitty_id),
+                Error::<T>::KittyInAuction
+            );
+            Ok(())
+        }
+
+        // 结算拍卖，最高出价支付给卖方并转移Kitty。无人出价，或最高出价者拥有数量已达上限时
+        // 退还出价，拍卖作废；最高出价者的质押不足以支付出价时同样作废并发出失败事件
+        fn settle_auction(kitty_id: T::KittyIndex) {
+            let auction = match Auctions::<T>::take(kitty_id) {
+                Some(auction) => auction,
+                None => return,
+            };
+
+            match auction.highest_bid {
+                Some((winner, amount)) if Self::ensure_can_own(&winner).is_ok() => {
+                    // 出价时已质押，扣除市场手续费及创建者版税，Kitty质押保留给新拥有者
+                    let (fee, royalty) = match Self::pay_sale_from_reserved(
+                        &winner,
+                        &auction.seller,
+                        kitty_id,
+                        amount,
+                    ) {
+                        Ok(paid) => paid,
+                        Err(_) => {
+                            T::Currency::unreserve_named(
+                                &RESERVE_ID,
+                                &winner,
+                                amount.saturating_add(auction.bid_deposit),
+                            );
+                            Self::deposit_event(Event::AuctionSettlementFailed(
+                                auction.seller,
+                                winner,
+                                kitty_id,
+                            ));
+                            return;
+                        }
+                    };
+                    Self::release_deposit(&auction.seller, kitty_id);
+                    KittyDeposits::<T>::insert(kitty_id, auction.bid_deposit);
+                    Self::change_owner(&auction.seller, &winner, kitty_id);
+                    Self::deposit_event(Event::AuctionSettled(
+                        auction.seller,
+                        winner,
+                        kitty_id,
+                        amount,
+                        fee,
+                        royalty,
+                    ));
+                }
+                Some((bidder, amount)) => {
+                    T::Currency::unreserve_named(
+                        &RESERVE_ID,
+                        &bidder,
+                        amount.saturating_add(auction.bid_deposit),
+                    );
+                    Self::deposit_event(Event::AuctionExpired(auction.seller, kitty_id));
+                }
+                None => Self::deposit_event(Event::AuctionExpired(auction.seller, kitty_id)),
+            }
+        }
+
+        // 买方以可用余额支付成交价，返回实际的 (手续费, 版税)
+        fn pay_sale(
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            kitty_id: T::KittyIndex,
+            price: BalanceOf<T>,
+        ) -> sp_std::result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let payment = T::Currency::withdraw(
+                buyer,
+                price,
+                WithdrawReasons::TRANSFER,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Self::split_sale(payment, seller, kitty_id)
+        }
+
+        // 以买方已质押的资金支付成交价，质押不足时回滚，不支付任何部分。返回实际的 (手续费, 版税)
+        #[transactional]
+        fn pay_sale_from_reserved(
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            kitty_id: T::KittyIndex,
+            price: BalanceOf<T>,
+        ) -> sp_std::result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let (payment, remainder) = T::Currency::slash_reserved_named(&RESERVE_ID, buyer, price);
+            ensure!(remainder.is_zero(), Error::<T>::MoneyNotEnough);
+            Self::split_sale(payment, seller, kitty_id)
+        }
+
+        // 分配成交价：市场手续费交由 OnMarketplaceFee 处理，版税支付给创建者，余下支付给卖方。
+        // 卖方即创建者或创建者账户已不存在时，版税归卖方。返回实际的 (手续费, 版税)
+        fn split_sale(
+            payment: NegativeImbalanceOf<T>,
+            seller: &T::AccountId,
+            kitty_id: T::KittyIndex,
+        ) -> sp_std::result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let price = payment.peek();
+            let creator = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?.creator;
+            let fee = Self::marketplace_fee() * price;
+            let mut royalty = if &creator != seller {
+                Self::creator_royalty() * price
+            } else {
+                Zero::zero()
+            };
+
+            let (fee_payment, rest) = payment.split(fee);
+            let (royalty_payment, mut rest) = rest.split(royalty);
+
+            T::OnMarketplaceFee::on_unbalanced(fee_payment);
+            if let Err(royalty_payment) =
+                T::Currency::resolve_into_existing(&creator, royalty_payment)
+            {
+                rest.subsume(royalty_payment);
+                royalty = Zero::zero();
+            }
+            T::Currency::resolve_creating(seller, rest);
+
+            Ok((fee, royalty))
+        }
+
+        // 买方以资产支付成交价：市场手续费支付给 MarketplaceAccount，版税支付给创建者，余下支付给卖方。
+        // 卖方即创建者或版税无法转入创建者账户时，版税归卖方。返回实际的 (手续费, 版税)
+        fn pay_sale_in_asset(
+            buyer: &T::AccountId,
+            seller: &T::AccountId,
+            kitty_id: T::KittyIndex,
+            asset_id: AssetIdOf<T>,
+            price: BalanceOf<T>,
+        ) -> sp_std::result::Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+            let creator = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyIndex)?.creator;
+            let fee = Self::marketplace_fee() * price;
+            let mut royalty = if &creator != seller {
+                Self::creator_royalty() * price
+            } else {
+                Zero::zero()
+            };
+
+            if !fee.is_zero() {
+                <T::Assets as fungibles::Transfer<_>>::transfer(
+                    asset_id,
+                    buyer,
+                    &T::MarketplaceAccount::get(),
+                    Self::to_asset_balance(fee)?,
+                    true,
+                )?;
+            }
+
+            if !royalty.is_zero()
+                && <T::Assets as fungibles::Transfer<_>>::transfer(
+                    asset_id,
+                    buyer,
+                    &creator,
+                    Self::to_asset_balance(royalty)?,
+                    true,
+                )
+                .is_err()
+            {
+                royalty = Zero::zero();
+            }
+            <T::Assets as fungibles::Transfer<_>>::transfer(
+                asset_id,
+                buyer,
+                seller,
+                Self::to_asset_balance(price.saturating_sub(fee).saturating_sub(royalty))?,
+                true,
+            )?;
+
+            Ok((fee, royalty))
+        }
+
+        // 将挂售价格转换为资产数量
+        fn to_asset_balance(
+            amount: BalanceOf<T>,
+        ) -> sp_std::result::Result<AssetBalanceOf<T>, DispatchError> {
+            AssetBalanceOf::<T>::try_from(amount.saturated_into::<u128>())
+                .map_err(|_| Error::<T>::PriceOverflow.into())
+        }
+
+        // 以当前 KittyReserve 质押并记录
+        fn reserve_deposit(who: &T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+            let deposit = Self::kitty_reserve();
+            T::Currency::reserve_named(&RESERVE_ID, who, deposit)
+                .map_err(|_| Error::<T>::MoneyNotEnough)?;
+            KittyDeposits::<T>::insert(kitty_id, deposit);
+            Ok(())
+        }
+
+        // 按记录解除质押
+        fn release_deposit(who: &T::AccountId, kitty_id: T::KittyIndex) {
+            let deposit = KittyDeposits::<T>::take(kitty_id);
+            T::Currency::unreserve_named(&RESERVE_ID, who, deposit);
+        }
+
+        // 新拥有者以当前 KittyReserve 质押，原拥有者按记录解除质押
+        fn transfer_deposit(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            kitty_id: T::KittyIndex,
+        ) -> DispatchResult {
+            let deposit = KittyDeposits::<T>::get(kitty_id);
+            Self::reserve_deposit(to, kitty_id)?;
+            T::Currency::unreserve_named(&RESERVE_ID, from, deposit);
+            Ok(())
+        }
+
+        // 移除报价并解除买方质押
+        fn release_offer(kitty_id: T::KittyIndex, bidder: &T::AccountId) {
+            if let Some(offer) = Offers::<T>::take(kitty_id, bidder) {
+                T::Currency::unreserve_named(
+                    &RESERVE_ID,
+                    bidder,
+                    offer.amount.saturating_add(offer.deposit),
+                );
+            }
+        }
+
+        // 移除元数据并退还设置者的质押，返回是否存在元数据
+        fn remove_metadata(kitty_id: T::KittyIndex) -> bool {
+            match Metadata::<T>::take(kitty_id) {
+                Some(metadata) => {
+                    T::Currency::unreserve_named(&RESERVE_ID, &metadata.depositor, metadata.deposit);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        // 记录等待接收方确认的转移请求
+        fn offer_transfer(from: T::AccountId, to: T::AccountId, kitty_id: T::KittyIndex) {
+            let expires_at = <frame_system::Pallet<T>>::block_number()
+                .saturating_add(T::TransferExpiry::get());
+            PendingTransfers::<T>::insert(
+                kitty_id,
+                PendingTransfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    expires_at,
+                },
+            );
+            Self::deposit_event(Event::KittyTransferOffered(from, to, kitty_id, expires_at));
+        }
+
+        // 判断 operator 是否被授权转移 owner 的全部Kitty
+        pub fn is_operator(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+            OperatorApprovals::<T>::contains_key(owner, operator)
+        }
+
+        // 更改拥有者，同步维护拥有索引并清除挂售、配种服务、未确认的转移请求及各类授权
+        fn change_owner(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex) {
+            Owner::<T>::insert(kitty_id, to.clone());
+            Self::remove_owned_kitty(from, kitty_id);
+            Self::add_owned_kitty(to, kitty_id);
+            PendingTransfers::<T>::remove(kitty_id);
+            SireApprovals::<T>::remove(kitty_id);
+            Self::cancel_sale(from, kitty_id);
+            if SirePrices::<T>::take(kitty_id).is_some() {
+                Self::deposit_event(Event::SireListingCancelled(from.clone(), kitty_id));
+            }
+            if Approvals::<T>::take(kitty_id).is_some() {
+                Self::deposit_event(Event::KittyApprovalCleared(from.clone(), kitty_id));
+            }
+        }
+
+        // 移除挂售，存在挂售时发出取消事件
+        fn cancel_sale(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+            DutchAuctions::<T>::remove(kitty_id);
+            KittyPriceAssets::<T>::remove(kitty_id);
+            if KittyPrices::<T>::take(kitty_id).is_some() {
+                Self::deposit_event(Event::KittySaleCancelled(owner.clone(), kitty_id));
+            }
+        }
+
+        fn add_owned_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+            OwnedKitties::<T>::insert(owner, kitty_id, ());
+            OwnedKittiesCount::<T>::mutate(owner, |count| *count = count.saturating_add(1));
+        }
+
+        fn remove_owned_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex) {
+            OwnedKitties::<T>::remove(owner, kitty_id);
+            OwnedKittiesCount::<T>::mutate(owner, |count| *count = count.saturating_sub(1));
         }
     }
 }
@@ -0,0 +1,273 @@
+//! Storage migrations for pallet-kitties.
+//!
+//! 链上存储版本记录于 `StorageVersion`，`migrate` 按版本依次执行各步迁移。
+//! 新增存储版本时，在 `Releases` 中追加版本，实现对应的 `migrate_to_vN`，
+//! 并在 `migrate` 末尾追加一步。旧版本的迁移只能按当时的存储格式读写，
+//! 不能使用已变更类型的存储项。
+
+use super::*;
+use crate::pallet::StorageVersion;
+use codec::Encode;
+use frame_support::{
+    storage::migration::{get_storage_value, put_storage_value, storage_key_iter},
+    traits::{Get, NamedReservableCurrency, PalletInfoAccess, ReservableCurrency},
+    weights::Weight,
+    Blake2_128Concat, StorageHasher,
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::traits::One;
+use sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+// 按存储版本依次执行迁移，返回消耗的权重
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if StorageVersion::<T>::get() == Releases::V1 {
+        weight = weight.saturating_add(migrate_to_v2::<T>());
+    }
+    if StorageVersion::<T>::get() == Releases::V2 {
+        weight = weight.saturating_add(migrate_to_v3::<T>(V2_KITTY_RESERVE.saturated_into()));
+    }
+    if StorageVersion::<T>::get() == Releases::V3 {
+        weight = weight.saturating_add(migrate_to_v4::<T>());
+    }
+    if StorageVersion::<T>::get() == Releases::V4 {
+        weight = weight.saturating_add(migrate_to_v5::<T>());
+    }
+    weight
+}
+
+// 存储前缀使用运行时中配置的 pallet 名称
+fn pallet_prefix<T: Config>() -> &'static [u8] {
+    <Pallet<T> as PalletInfoAccess>::name().as_bytes()
+}
+
+fn key_hash<T: Config>(kitty_id: &T::KittyIndex) -> Vec<u8> {
+    kitty_id.using_encoded(Blake2_128Concat::hash)
+}
+
+// Kitties、Owner、KittyPrices 在 V4 之前以 ValueQuery 存储 Option<_>，按旧格式读取拥有者
+fn v3_owner<T: Config>(kitty_id: &T::KittyIndex) -> Option<T::AccountId> {
+    let hash = key_hash::<T>(kitty_id);
+    get_storage_value::<Option<T::AccountId>>(pallet_prefix::<T>(), b"Owner", &hash).flatten()
+}
+
+// V1 版本的存储结构
+pub mod v1 {
+    use codec::Decode;
+
+    #[derive(Decode)]
+    pub struct Kitty(pub [u8; 16]);
+}
+
+// V3 之前每个Kitty、英式拍卖出价及报价以匿名方式质押的数量，即当时运行时的 KittyReserve。
+// 迁移不能读取 T::KittyReserve，其值在之后的版本中可能已被修改
+pub const V2_KITTY_RESERVE: u128 = 1_000;
+
+// V2 版本的存储结构
+pub mod v2 {
+    use codec::Decode;
+
+    #[derive(Decode)]
+    pub struct Auction<AccountId, Balance, BlockNumber> {
+        pub seller: AccountId,
+        pub reserve_price: Balance,
+        pub end_block: BlockNumber,
+        pub highest_bid: Option<(AccountId, Balance)>,
+    }
+
+    #[derive(Decode)]
+    pub struct Offer<Balance, BlockNumber> {
+        pub amount: Balance,
+        pub expires_at: BlockNumber,
+    }
+}
+
+// V1 -> V2: Kitty 增加血统信息，已有Kitty视为第0代，创建者为当前拥有者
+pub fn migrate_to_v2<T: Config>() -> Weight {
+    let pallet = pallet_prefix::<T>();
+    let old: Vec<(T::KittyIndex, Option<v1::Kitty>)> =
+        storage_key_iter::<T::KittyIndex, Option<v1::Kitty>, Blake2_128Concat>(pallet, b"Kitties")
+            .collect();
+    let translated = old.len() as u64;
+
+    for (kitty_id, old) in old {
+        let kitty: Option<KittyOf<T>> = old.map(|old| Kitty {
+            dna: old.0,
+            parents: None,
+            generation: 0,
+            birth_block: Zero::zero(),
+            creator: v3_owner::<T>(&kitty_id).unwrap_or_default(),
+        });
+        put_storage_value(pallet, b"Kitties", &key_hash::<T>(&kitty_id), kitty);
+    }
+    StorageVersion::<T>::put(Releases::V2);
+
+    T::DbWeight::get().reads_writes(2 * translated + 1, translated + 1)
+}
+
+// 将匿名质押转为以 RESERVE_ID 命名的质押，返回实际转换的数量
+fn move_to_named<T: Config>(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+    let moved = amount.saturating_sub(T::Currency::unreserve(who, amount));
+    match T::Currency::reserve_named(&RESERVE_ID, who, moved) {
+        Ok(()) => moved,
+        Err(_) => Zero::zero(),
+    }
+}
+
+// V2 -> V3: Kitty、英式拍卖出价及报价的质押转为命名质押，并补录每个Kitty的质押。
+// old_reserve 为 V2 时的质押数量，每个账户转换的数量不超过其按该数量应质押的总额，
+// 避免将其他模块的匿名质押转入 RESERVE_ID
+pub fn migrate_to_v3<T: Config>(old_reserve: BalanceOf<T>) -> Weight {
+    let mut migrated = 0u64;
+
+    let mut owned: BTreeMap<T::AccountId, Vec<T::KittyIndex>> = BTreeMap::new();
+    let owners = storage_key_iter::<T::KittyIndex, Option<T::AccountId>, Blake2_128Concat>(
+        pallet_prefix::<T>(),
+        b"Owner",
+    );
+    for (kitty_id, owner) in owners {
+        if let Some(owner) = owner {
+            owned.entry(owner).or_insert_with(Vec::new).push(kitty_id);
+        }
+    }
+    for (owner, kitties) in owned {
+        let total = old_reserve.saturating_mul((kitties.len() as u32).into());
+        let mut moved = move_to_named::<T>(&owner, total);
+        // 依次为每个Kitty记录 old_reserve，实际转换的数量不足时后面的Kitty记录剩余部分
+        for kitty_id in kitties {
+            let kitty_deposit = moved.min(old_reserve);
+            moved = moved.saturating_sub(kitty_deposit);
+            KittyDeposits::<T>::insert(kitty_id, kitty_deposit);
+            migrated += 1;
+        }
+    }
+
+    Auctions::<T>::translate::<v2::Auction<T::AccountId, BalanceOf<T>, T::BlockNumber>, _>(
+        |_, old| {
+            migrated += 1;
+            let bid_deposit = match &old.highest_bid {
+                Some((bidder, amount)) => {
+                    move_to_named::<T>(bidder, amount.saturating_add(old_reserve))
+                        .saturating_sub(*amount)
+                }
+                None => Zero::zero(),
+            };
+            Some(Auction {
+                seller: old.seller,
+                reserve_price: old.reserve_price,
+                end_block: old.end_block,
+                highest_bid: old.highest_bid,
+                bid_deposit,
+            })
+        },
+    );
+
+    Offers::<T>::translate::<v2::Offer<BalanceOf<T>, T::BlockNumber>, _>(|_, bidder, old| {
+        migrated += 1;
+        let moved = move_to_named::<T>(&bidder, old.amount.saturating_add(old_reserve));
+        Some(Offer {
+            amount: old.amount,
+            expires_at: old.expires_at,
+            deposit: moved.saturating_sub(old.amount),
+        })
+    });
+
+    StorageVersion::<T>::put(Releases::V3);
+
+    T::DbWeight::get().reads_writes(4 * migrated + 1, 4 * migrated + 1)
+}
+
+// V3 -> V4: Kitties、Owner、KittyPrices 由 ValueQuery<Option<_>> 改为 OptionQuery，
+// 原先存储的 None 值直接删除
+pub fn migrate_to_v4<T: Config>() -> Weight {
+    let mut translated = 0u64;
+
+    Kitties::<T>::translate::<Option<KittyOf<T>>, _>(|_, kitty| {
+        translated += 1;
+        kitty
+    });
+    Owner::<T>::translate::<Option<T::AccountId>, _>(|_, owner| {
+        translated += 1;
+        owner
+    });
+    KittyPrices::<T>::translate::<Option<BalanceOf<T>>, _>(|_, price| {
+        translated += 1;
+        price
+    });
+
+    StorageVersion::<T>::put(Releases::V4);
+
+    T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
+
+// V4 -> V5: 由 Owner 补建拥有索引，引入 OwnedKitties 之前创建的Kitty没有索引记录
+pub fn migrate_to_v5<T: Config>() -> Weight {
+    let mut counts: BTreeMap<T::AccountId, u32> = BTreeMap::new();
+    let mut indexed = 0u64;
+
+    for (kitty_id, owner) in Owner::<T>::iter() {
+        OwnedKitties::<T>::insert(&owner, kitty_id, ());
+        let count = counts.entry(owner).or_insert(0);
+        *count = count.saturating_add(1);
+        indexed += 1;
+    }
+    let owners = counts.len() as u64;
+    for (owner, count) in counts {
+        OwnedKittiesCount::<T>::insert(owner, count);
+    }
+
+    StorageVersion::<T>::put(Releases::V5);
+
+    T::DbWeight::get().reads_writes(indexed + 1, indexed + owners + 1)
+}
+
+// 升级前检查：每个Kitty同时存在 Kitties 与 Owner 记录，挂售的Kitty必须存在
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+    ensure_consistent_keys::<T>()
+}
+
+// 升级后检查：存储版本为最新，且所有记录都能按新格式解码
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+    if StorageVersion::<T>::get() != Releases::V5 {
+        return Err("kitties storage version was not bumped to V5");
+    }
+    ensure_consistent_keys::<T>()?;
+
+    let count = KittiesCount::<T>::get();
+    let mut kitty_id = T::KittyIndex::zero();
+    while kitty_id < count {
+        if Kitties::<T>::contains_key(kitty_id) {
+            let owner = Owner::<T>::get(kitty_id).ok_or("kitty owner failed to decode")?;
+            Kitties::<T>::get(kitty_id).ok_or("kitty failed to decode")?;
+            if !OwnedKitties::<T>::contains_key(&owner, kitty_id) {
+                return Err("kitty missing from owner index");
+            }
+        }
+        if KittyPrices::<T>::contains_key(kitty_id) && KittyPrices::<T>::get(kitty_id).is_none()
+        {
+            return Err("kitty price failed to decode");
+        }
+        kitty_id += One::one();
+    }
+    Ok(())
+}
+
+#[cfg(feature = "try-runtime")]
+fn ensure_consistent_keys<T: Config>() -> Result<(), &'static str> {
+    let count = KittiesCount::<T>::get();
+    let mut kitty_id = T::KittyIndex::zero();
+    while kitty_id < count {
+        let exists = Kitties::<T>::contains_key(kitty_id);
+        if exists != Owner::<T>::contains_key(kitty_id) {
+            return Err("Kitties and Owner keys are out of sync");
+        }
+        if !exists && KittyPrices::<T>::contains_key(kitty_id) {
+            return Err("price listed for a missing kitty");
+        }
+        kitty_id += One::one();
+    }
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use crate as pallet_kitties;
-use frame_support::parameter_types;
+use frame_support::{
+    parameter_types,
+    traits::{Currency, OnUnbalanced},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -19,7 +23,8 @@ frame_support::construct_runtime!(
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-        KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        KittiesModule: pallet_kitties::{Pallet, Call, Storage, Config<T>, Event<T>},
     }
 );
 
@@ -62,11 +67,12 @@ pub type Balance = u128;
 parameter_types! {
     pub const ExistentialDeposit: u128 = 500;
     pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Test {
     type MaxLocks = MaxLocks;
-    type MaxReserves = ();
+    type MaxReserves = MaxReserves;
     type ReserveIdentifier = [u8; 8];
     /// The type for recording an account's balance.
     type Balance = u128;
@@ -80,8 +86,67 @@ impl pallet_balances::Config for Test {
 
 impl pallet_randomness_collective_flip::Config for Test {}
 
+parameter_types! {
+    pub const AssetDeposit: u128 = 0;
+    pub const ApprovalDeposit: u128 = 0;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: u128 = 0;
+    pub const MetadataDepositPerByte: u128 = 0;
+}
+
+impl pallet_assets::Config for Test {
+    type Event = Event;
+    type Balance = u64;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const KittyReserve: u64 = 1_000;
+    pub const TransferExpiry: u64 = 10;
+    pub const BaseCooldown: u64 = 5;
+    pub const MaxCooldown: u64 = 100;
+    pub const MaxAuctionDuration: u64 = 100;
+    pub const AuctionExtension: u64 = 3;
+    pub const MaxAuctionsPerBlock: u32 = 2;
+    pub const MaxOfferDuration: u64 = 100;
+    pub const MaxKittiesPerAccount: u32 = 5;
+    pub const KittyStringLimit: u32 = 32;
+    pub const KittyMetadataDepositBase: u64 = 100;
+    pub const KittyMetadataDepositPerByte: u64 = 10;
+    pub const MarketplaceFee: Permill = Permill::from_percent(5);
+    pub const CreatorRoyalty: Permill = Permill::from_percent(10);
+    pub const MarketplaceAccount: u64 = FEE_ACCOUNT;
+}
+
+// 收取市场手续费的账户
+pub const FEE_ACCOUNT: u64 = 100;
+
+pub struct MarketplaceFeeReceiver;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for MarketplaceFeeReceiver {
+    fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Test>) {
+        Balances::resolve_creating(&FEE_ACCOUNT, amount);
+    }
+}
+
+// 基准测试中创建可供任意账户持有的资产
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetsBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_kitties::BenchmarkHelper<u32> for AssetsBenchmarkHelper {
+    fn create_asset(asset_id: u32) {
+        Assets::force_create(Origin::root(), asset_id, 0, true, 1)
+            .expect("asset does not exist yet; qed");
+    }
 }
 
 impl pallet_kitties::Config for Test {
@@ -90,19 +155,50 @@ impl pallet_kitties::Config for Test {
     type KittyIndex = u32;
     type KittyReserve = KittyReserve;
     type Currency = Balances;
+    type TransferExpiry = TransferExpiry;
+    type BaseCooldown = BaseCooldown;
+    type MaxCooldown = MaxCooldown;
+    type MaxAuctionDuration = MaxAuctionDuration;
+    type AuctionExtension = AuctionExtension;
+    type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+    type MaxOfferDuration = MaxOfferDuration;
+    type MaxKittiesPerAccount = MaxKittiesPerAccount;
+    type StringLimit = KittyStringLimit;
+    type MetadataDepositBase = KittyMetadataDepositBase;
+    type MetadataDepositPerByte = KittyMetadataDepositPerByte;
+    type MarketplaceFee = MarketplaceFee;
+    type CreatorRoyalty = CreatorRoyalty;
+    type OnMarketplaceFee = MarketplaceFeeReceiver;
+    type MarketplaceAccount = MarketplaceAccount;
+    type Assets = Assets;
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = AssetsBenchmarkHelper;
+    type WeightInfo = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+    new_test_ext_with_kitties(vec![])
+}
+
+// 使用创世Kitty构建测试环境
+pub fn new_test_ext_with_kitties(
+    kitties: Vec<(u64, Option<[u8; 16]>)>,
+) -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
 
     pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(1, 10000), (2, 20000), (3, 30000)],
+        balances: vec![(1, 10000), (2, 20000), (3, 30000), (FEE_ACCOUNT, 1000)],
     }
         .assimilate_storage(&mut t)
         .unwrap();
 
+    pallet_kitties::GenesisConfig::<Test> { kitties }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
     ext
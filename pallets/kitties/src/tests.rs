@@ -1,15 +1,42 @@
 use super::*;
 use crate::{mock::*, Error};
 use frame_support::{assert_noop, assert_ok};
-use frame_support::traits::{OnFinalize, OnInitialize};
+use codec::Encode;
+use frame_support::traits::{
+    NamedReservableCurrency, OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade, Randomness,
+    ReservableCurrency,
+};
+use frame_support::weights::Weight;
+use sp_runtime::{traits::BadOrigin, Permill};
 
 pub const KITTY_RESERVE: u128 = 1_000;
 pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
 pub const NOBODY: u64 = 99;
 
+// 设置Kitty性别
+fn set_gender(kitty_id: u32, gender: Gender) {
+    Kitties::<Test>::mutate(kitty_id, |kitty| {
+        if let Some(kitty) = kitty {
+            kitty.dna[GENDER_BYTE] = match gender {
+                Gender::Male => 0,
+                Gender::Female => 1,
+            };
+        }
+    });
+}
+
+// 创建指定性别的Kitty
+fn create_kitty(owner: u64, gender: Gender) -> u32 {
+    let kitty_id = KittiesCount::<Test>::get();
+    assert_ok!(KittiesModule::create(Origin::signed(owner)));
+    set_gender(kitty_id, gender);
+    kitty_id
+}
+
 fn run_to_block( n: u64) {
     while System::block_number() < n {
+        KittiesModule::on_idle(System::block_number(), Weight::max_value());
         KittiesModule::on_finalize(System::block_number());
         System::on_finalize(System::block_number());
         System::set_block_number(System::block_number()+1);
@@ -57,12 +84,26 @@ fn can_transfer_work() {
         //检查质押数量
         assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
 
-        //检查转移Kitty
+        //检查发起转移Kitty
         assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
         //检查事件
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyTransferOffered(ALICE, BOB, 0, 1 + TransferExpiry::get()),
+        ));
+        //确认前拥有者及质押不变
+        assert_eq!(Owner::<Test>::get(0), Some(ALICE));
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+
+        //接收方确认转移
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        //检查事件
         System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyTransfer(
             ALICE, BOB, 0,
         )));
+        //检查拥有者
+        assert_eq!(Owner::<Test>::get(0), Some(BOB));
+        assert_eq!(PendingTransfers::<Test>::get(0), None);
         //检查质押数量
         assert_eq!(Balances::reserved_balance(ALICE), 0);
         //检查质押数量
@@ -73,9 +114,11 @@ fn can_transfer_work() {
 #[test]
 fn can_transfer_faile_not_enough_money() {
     new_test_ext().execute_with(|| {
-        //检查质押不足时创建Kitty，是否返回正确错误
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), NOBODY, 0));
+        //检查接收方质押不足时确认转移，是否返回正确错误
         assert_noop!(
-            KittiesModule::create(Origin::signed(NOBODY)),
+            KittiesModule::accept_transfer(Origin::signed(NOBODY), 0),
             Error::<Test>::MoneyNotEnough
         );
     });
@@ -106,8 +149,8 @@ fn can_transfer_failed_already_owned() {
 #[test]
 fn can_bread_work() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
-        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
         assert_eq!(KittiesCount::<Test>::get(), 2);
         assert_eq!(Owner::<Test>::get(0), Some(ALICE));
         assert_eq!(Owner::<Test>::get(1), Some(ALICE));
@@ -161,9 +204,7 @@ fn can_sale_work() {
         assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(5_000)));
         //检查事件
         System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyForSale(
-            ALICE,
-            0,
-            Some(5_000),
+            ALICE, 0, 5_000,
         )));
     });
 }
@@ -238,11 +279,15 @@ fn can_buy_work() {
         System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
             BOB,
             0,
-            Some(8_000),
+            8_000,
+            400,
+            0,
         )));
 
-        //检查是否已经收到转账
-        assert_eq!(Balances::free_balance(ALICE), 10_000 + 8_000);
+        //检查是否已经收到转账，卖方即创建者时无版税
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 8_000 - 400);
+        //检查市场手续费
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), 1_000 + 400);
         //检查是否已经转出
         assert_eq!(
             Balances::free_balance(BOB),
@@ -259,4 +304,2103 @@ fn can_buy_work() {
         //检查挂单
         assert_eq!(KittyPrices::<Test>::get(0), None);
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn can_owned_kitties_work() {
+    new_test_ext().execute_with(|| {
+        //创建及繁殖Kittiy
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+        //检查拥有列表及数量
+        assert_eq!(KittiesModule::kitties_of(&ALICE), vec![0, 1, 2]);
+        assert_eq!(OwnedKittiesCount::<Test>::get(ALICE), 3);
+
+        //转移Kitty后检查拥有列表
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        assert_eq!(KittiesModule::kitties_of(&ALICE), vec![1, 2]);
+        assert_eq!(KittiesModule::kitties_of(&BOB), vec![0]);
+        assert_eq!(OwnedKittiesCount::<Test>::get(ALICE), 2);
+        assert_eq!(OwnedKittiesCount::<Test>::get(BOB), 1);
+
+        //购买Kitty后检查拥有列表
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 2, Some(5_000)));
+        assert_ok!(KittiesModule::buy(Origin::signed(BOB), 2));
+        assert_eq!(KittiesModule::kitties_of(&ALICE), vec![1]);
+        assert_eq!(KittiesModule::kitties_of(&BOB), vec![0, 2]);
+        assert!(KittiesModule::is_owned_by(&BOB, 2));
+        assert!(!KittiesModule::is_owned_by(&ALICE, 2));
+        assert_eq!(OwnedKittiesCount::<Test>::get(ALICE), 1);
+        assert_eq!(OwnedKittiesCount::<Test>::get(BOB), 2);
+    });
+}
+
+#[test]
+fn can_accept_transfer_failed_not_recipient() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        //检查非接收方确认转移，是否返回正确错误
+        assert_noop!(
+            KittiesModule::accept_transfer(Origin::signed(ALICE), 0),
+            Error::<Test>::NotTransferRecipient
+        );
+        //检查没有转移请求时确认，是否返回正确错误
+        assert_noop!(
+            KittiesModule::accept_transfer(Origin::signed(BOB), 1),
+            Error::<Test>::NoPendingTransfer
+        );
+    });
+}
+
+#[test]
+fn can_accept_transfer_failed_expired() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        //超过有效期后确认转移，是否返回正确错误
+        run_to_block(2 + TransferExpiry::get());
+        assert_noop!(
+            KittiesModule::accept_transfer(Origin::signed(BOB), 0),
+            Error::<Test>::TransferExpired
+        );
+        assert_eq!(Owner::<Test>::get(0), Some(ALICE));
+    });
+}
+
+#[test]
+fn can_reject_transfer_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        //检查非接收方拒绝转移，是否返回正确错误
+        assert_noop!(
+            KittiesModule::reject_transfer(Origin::signed(ALICE), 0),
+            Error::<Test>::NotTransferRecipient
+        );
+
+        //接收方拒绝转移
+        assert_ok!(KittiesModule::reject_transfer(Origin::signed(BOB), 0));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyTransferRejected(BOB, 0),
+        ));
+        assert_eq!(PendingTransfers::<Test>::get(0), None);
+        assert_eq!(Owner::<Test>::get(0), Some(ALICE));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+    });
+}
+
+#[test]
+fn can_cancel_transfer_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        //检查非发起方取消转移，是否返回正确错误
+        assert_noop!(
+            KittiesModule::cancel_transfer(Origin::signed(BOB), 0),
+            Error::<Test>::NotOwner
+        );
+
+        //发起方取消转移
+        assert_ok!(KittiesModule::cancel_transfer(Origin::signed(ALICE), 0));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyTransferCancelled(ALICE, 0),
+        ));
+        //取消后无法确认
+        assert_noop!(
+            KittiesModule::accept_transfer(Origin::signed(BOB), 0),
+            Error::<Test>::NoPendingTransfer
+        );
+    });
+}
+
+#[test]
+fn can_bread_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        //检查使用他人Kitty作为母方繁殖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(BOB), 0, 1),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn can_bread_failed_not_sire_approved() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::create(Origin::signed(BOB)));
+        //检查未获授权使用他人Kitty作为父方繁殖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(BOB), 1, 0),
+            Error::<Test>::NotSireApproved
+        );
+    });
+}
+
+#[test]
+fn can_approve_siring_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(BOB, Gender::Female);
+        //检查非拥有者授权，是否返回正确错误
+        assert_noop!(
+            KittiesModule::approve_siring(Origin::signed(BOB), 0, BOB),
+            Error::<Test>::NotOwner
+        );
+
+        //授权BOB使用Kitty 0作为父方
+        assert_ok!(KittiesModule::approve_siring(Origin::signed(ALICE), 0, BOB));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::SiringApproved(
+            ALICE, 0, BOB,
+        )));
+        assert_eq!(SireApprovals::<Test>::get(0), Some(BOB));
+
+        //繁殖后幼崽归母方拥有者，授权失效
+        assert_ok!(KittiesModule::bread(Origin::signed(BOB), 1, 0));
+        assert_eq!(Owner::<Test>::get(2), Some(BOB));
+        assert_eq!(SireApprovals::<Test>::get(0), None);
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(BOB), 1, 0),
+            Error::<Test>::NotSireApproved
+        );
+    });
+}
+
+#[test]
+fn can_revoke_siring_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(BOB, Gender::Female);
+        //检查未授权时撤销，是否返回正确错误
+        assert_noop!(
+            KittiesModule::revoke_siring(Origin::signed(ALICE), 0),
+            Error::<Test>::NotSireApproved
+        );
+
+        assert_ok!(KittiesModule::approve_siring(Origin::signed(ALICE), 0, BOB));
+        assert_ok!(KittiesModule::revoke_siring(Origin::signed(ALICE), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::SiringRevoked(
+            ALICE, 0,
+        )));
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(BOB), 1, 0),
+            Error::<Test>::NotSireApproved
+        );
+    });
+}
+
+#[test]
+fn can_sire_approval_cleared_on_transfer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::approve_siring(Origin::signed(ALICE), 0, BOB));
+        //转移后原拥有者的授权失效
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        assert_eq!(SireApprovals::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn can_cancel_sale_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        //检查未挂售时取消挂售，是否返回正确错误
+        assert_noop!(
+            KittiesModule::sale(Origin::signed(ALICE), 0, None),
+            Error::<Test>::NotForSale
+        );
+
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(5_000)));
+        //取消挂售
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, None));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittySaleCancelled(ALICE, 0),
+        ));
+        assert_eq!(KittyPrices::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn can_sale_cleared_on_transfer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(5_000)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        //转移后原挂售被移除
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        System::assert_has_event(mock::Event::KittiesModule(
+            crate::Event::KittySaleCancelled(ALICE, 0),
+        ));
+        assert_eq!(KittyPrices::<Test>::get(0), None);
+
+        //检查按原挂售价格购买，是否返回正确错误
+        assert_noop!(
+            KittiesModule::buy(Origin::signed(ALICE), 0),
+            Error::<Test>::NotForSale
+        );
+    });
+}
+
+#[test]
+fn can_genesis_config_work() {
+    new_test_ext_with_kitties(vec![(ALICE, Some([7u8; 16])), (ALICE, None), (BOB, None)])
+        .execute_with(|| {
+            //检查创世Kitty
+            assert_eq!(KittiesCount::<Test>::get(), 3);
+            assert_eq!(KittiesModule::kitties(0).map(|kitty| kitty.dna), Some([7u8; 16]));
+            assert!(KittiesModule::kitties(1).is_some());
+            assert_eq!(Owner::<Test>::get(1), Some(ALICE));
+            assert_eq!(Owner::<Test>::get(2), Some(BOB));
+            assert_eq!(KittiesModule::kitties_of(&ALICE), vec![0, 1]);
+            //检查质押数量
+            assert_eq!(Balances::reserved_balance(ALICE), 2 * KITTY_RESERVE);
+            assert_eq!(Balances::reserved_balance(BOB), KITTY_RESERVE);
+
+            //新创建的Kitty索引接续创世Kitty
+            assert_ok!(KittiesModule::create(Origin::signed(BOB)));
+            assert_eq!(Owner::<Test>::get(3), Some(BOB));
+        });
+}
+
+#[test]
+#[should_panic(expected = "genesis kitty owner must not exceed MaxKittiesPerAccount")]
+fn can_genesis_config_failed_on_too_many_kitties() {
+    //检查创世Kitty超出每个账户的拥有上限，是否中止构建
+    new_test_ext_with_kitties(vec![(ALICE, None); MaxKittiesPerAccount::get() as usize + 1]);
+}
+
+#[test]
+fn can_query_kitties_work() {
+    new_test_ext_with_kitties(vec![
+        (ALICE, Some([1u8; 16])),
+        (BOB, Some([2u8; 16])),
+        (ALICE, Some([3u8; 16])),
+        (ALICE, Some([4u8; 16])),
+    ])
+    .execute_with(|| {
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 2, Some(5_000)));
+        assert_ok!(KittiesModule::sale(Origin::signed(BOB), 1, Some(3_000)));
+
+        //查询单个Kitty
+        assert_eq!(
+            KittiesModule::kitty_info(2),
+            Some(KittyInfo {
+                dna: [3u8; 16],
+                owner: ALICE,
+                price: Some(5_000),
+                price_asset: None,
+                parents: None,
+                generation: 0,
+                birth_block: 0,
+                creator: ALICE,
+                burned_at: None,
+            })
+        );
+        assert_eq!(KittiesModule::kitty_info(9), None);
+
+        //分页查询拥有的Kitty
+        assert_eq!(KittiesModule::kitties_of_owner(&ALICE, None, 2), vec![0, 2]);
+        assert_eq!(KittiesModule::kitties_of_owner(&ALICE, Some(2), 2), vec![3]);
+        assert_eq!(KittiesModule::kitties_of_owner(&NOBODY, None, 10), vec![]);
+
+        //分页查询挂售
+        assert_eq!(
+            KittiesModule::kitties_for_sale(None, 10),
+            vec![(1, 3_000, None), (2, 5_000, None)]
+        );
+        assert_eq!(KittiesModule::kitties_for_sale(Some(1), 10), vec![(2, 5_000, None)]);
+        assert_eq!(KittiesModule::kitties_for_sale(None, 1), vec![(1, 3_000, None)]);
+    });
+}
+
+#[test]
+fn can_bread_record_lineage() {
+    new_test_ext().execute_with(|| {
+        run_to_block(5);
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
+        //检查创建的Kitty血统
+        let kitty = KittiesModule::kitties(0).unwrap();
+        assert_eq!(kitty.parents, None);
+        assert_eq!(kitty.generation, 0);
+        assert_eq!(kitty.birth_block, 5);
+        assert_eq!(kitty.creator, ALICE);
+
+        //检查繁殖的Kitty血统
+        run_to_block(8);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+        let kitty = KittiesModule::kitties(2).unwrap();
+        assert_eq!(kitty.parents, Some((0, 1)));
+        assert_eq!(kitty.generation, 1);
+        assert_eq!(kitty.birth_block, 8);
+        assert_eq!(kitty.creator, ALICE);
+
+        //代数为父母代数的最大值 + 1
+        run_to_block(8 + 2 * BaseCooldown::get());
+        set_gender(2, Gender::Male);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 2, 0));
+        assert_eq!(KittiesModule::kitties(3).unwrap().generation, 2);
+    });
+}
+
+#[test]
+fn can_migrate_to_v2_work() {
+    new_test_ext().execute_with(|| {
+        //写入V1格式的Kitty
+        frame_support::storage::unhashed::put(
+            &Kitties::<Test>::hashed_key_for(0),
+            &Some([5u8; 16]),
+        );
+        frame_support::storage::unhashed::put(&Owner::<Test>::hashed_key_for(0), &Some(BOB));
+        crate::pallet::StorageVersion::<Test>::put(Releases::V1);
+
+        KittiesModule::on_runtime_upgrade();
+
+        let kitty = KittiesModule::kitties(0).unwrap();
+        assert_eq!(kitty.dna, [5u8; 16]);
+        assert_eq!(kitty.parents, None);
+        assert_eq!(kitty.generation, 0);
+        assert_eq!(kitty.creator, BOB);
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V5);
+    });
+}
+
+#[test]
+fn can_bread_failed_on_cooldown() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(ALICE, Gender::Female);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+
+        //检查冷却期内繁殖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(ALICE), 0, 2),
+            Error::<Test>::KittyOnCooldown
+        );
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(ALICE), 2, 1),
+            Error::<Test>::KittyOnCooldown
+        );
+    });
+}
+
+#[test]
+fn can_cooldown_grow_with_breed_count() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
+        assert_eq!(KittiesModule::breeding_ready_at(0), Some(0));
+
+        //第一次繁殖后冷却 BaseCooldown
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+        let ready_at = 1 + BaseCooldown::get();
+        assert_eq!(KittiesModule::breeding_ready_at(0), Some(ready_at));
+        assert_eq!(KittiesModule::breeding_ready_at(1), Some(ready_at));
+        assert_eq!(Cooldowns::<Test>::get(0).breed_count, 1);
+        //幼崽为第1代，首次冷却 BaseCooldown
+        assert_eq!(KittiesModule::breeding_ready_at(2), Some(ready_at));
+        assert_eq!(Cooldowns::<Test>::get(2).breed_count, 0);
+
+        //第二次繁殖后冷却翻倍
+        run_to_block(ready_at);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+        assert_eq!(
+            KittiesModule::breeding_ready_at(0),
+            Some(ready_at + 2 * BaseCooldown::get())
+        );
+        assert_eq!(KittiesModule::breeding_ready_at(9), None);
+    });
+}
+
+#[test]
+fn can_cooldown_period_work() {
+    new_test_ext().execute_with(|| {
+        //冷却随繁殖次数及代数增长，且不超过上限
+        assert_eq!(KittiesModule::cooldown_period(0, 0), 5);
+        assert_eq!(KittiesModule::cooldown_period(0, 1), 5);
+        assert_eq!(KittiesModule::cooldown_period(0, 2), 10);
+        assert_eq!(KittiesModule::cooldown_period(2, 2), 40);
+        assert_eq!(KittiesModule::cooldown_period(10, 0), MaxCooldown::get());
+        assert_eq!(KittiesModule::cooldown_period(u32::MAX, u32::MAX), MaxCooldown::get());
+    });
+}
+
+#[test]
+fn can_bread_failed_incompatible_gender() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Female);
+        //检查同性别繁殖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(ALICE), 0, 1),
+            Error::<Test>::IncompatibleGender
+        );
+
+        //一雄一雌可繁殖，顺序不限
+        set_gender(0, Gender::Male);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+    });
+}
+
+#[test]
+fn can_gender_from_dna_work() {
+    new_test_ext_with_kitties(vec![
+        (ALICE, Some([0u8; 16])),
+        (ALICE, Some([0xffu8; 16])),
+        (ALICE, Some([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xfe])),
+    ])
+    .execute_with(|| {
+        //性别由DNA指定位决定
+        assert_eq!(KittiesModule::gender_of(0), Some(Gender::Male));
+        assert_eq!(KittiesModule::gender_of(1), Some(Gender::Female));
+        assert_eq!(KittiesModule::gender_of(2), Some(Gender::Male));
+        assert_eq!(KittiesModule::gender_of(9), None);
+    });
+}
+
+#[test]
+fn can_list_sire_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查非拥有者挂出配种服务，是否返回正确错误
+        assert_noop!(
+            KittiesModule::list_sire(Origin::signed(BOB), 0, Some(2_000)),
+            Error::<Test>::NotOwner
+        );
+        //检查未挂出时取消，是否返回正确错误
+        assert_noop!(
+            KittiesModule::list_sire(Origin::signed(ALICE), 0, None),
+            Error::<Test>::SireNotListed
+        );
+
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(2_000)));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::SireForSale(
+            ALICE, 0, 2_000,
+        )));
+        assert_eq!(SirePrices::<Test>::get(0), Some(2_000));
+
+        //取消配种服务
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, None));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::SireListingCancelled(ALICE, 0),
+        ));
+        assert_eq!(SirePrices::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn can_buy_siring_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(BOB, Gender::Female);
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(2_000)));
+
+        //支付配种费用繁殖
+        assert_ok!(KittiesModule::buy_siring(Origin::signed(BOB), 1, 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::SiringPurchased(
+            BOB, 1, 0, 2_000,
+        )));
+        //幼崽归母方拥有者
+        assert_eq!(Owner::<Test>::get(2), Some(BOB));
+        assert_eq!(KittiesModule::kitties(2).unwrap().parents, Some((1, 0)));
+        //配种费用支付给父方拥有者
+        assert_eq!(Balances::free_balance(ALICE), 10_000 - KITTY_RESERVE + 2_000);
+        assert_eq!(
+            Balances::free_balance(BOB),
+            20_000 - 2 * KITTY_RESERVE - 2_000
+        );
+        //配种服务保留，父方进入冷却
+        assert_eq!(SirePrices::<Test>::get(0), Some(2_000));
+        assert_noop!(
+            KittiesModule::buy_siring(Origin::signed(BOB), 1, 0),
+            Error::<Test>::KittyOnCooldown
+        );
+    });
+}
+
+#[test]
+fn can_buy_siring_failed() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(BOB, Gender::Female);
+        create_kitty(ALICE, Gender::Female);
+        //检查父方未挂出，是否返回正确错误
+        assert_noop!(
+            KittiesModule::buy_siring(Origin::signed(BOB), 1, 0),
+            Error::<Test>::SireNotListed
+        );
+
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(2_000)));
+        //检查母方非本人所有，是否返回正确错误
+        assert_noop!(
+            KittiesModule::buy_siring(Origin::signed(BOB), 2, 0),
+            Error::<Test>::NotOwner
+        );
+        //检查使用本人挂出的父方，是否返回正确错误
+        assert_noop!(
+            KittiesModule::buy_siring(Origin::signed(ALICE), 2, 0),
+            Error::<Test>::AlreadyOwned
+        );
+        //检查余额不足时，是否返回正确错误且不扣除费用
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(50_000)));
+        assert_noop!(
+            KittiesModule::buy_siring(Origin::signed(BOB), 1, 0),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn can_sire_listing_cleared_on_transfer() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(2_000)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        //转移后原配种服务被移除
+        System::assert_has_event(mock::Event::KittiesModule(
+            crate::Event::SireListingCancelled(ALICE, 0),
+        ));
+        assert_eq!(SirePrices::<Test>::get(0), None);
+    });
+}
+
+#[test]
+fn can_burn_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)));
+        assert_ok!(KittiesModule::list_sire(Origin::signed(ALICE), 0, Some(500)));
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
+
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 0, 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyBurned(ALICE, 0)));
+        //检查挂售及配种服务的取消事件
+        System::assert_has_event(mock::Event::KittiesModule(crate::Event::KittySaleCancelled(
+            ALICE, 0,
+        )));
+        System::assert_has_event(mock::Event::KittiesModule(
+            crate::Event::SireListingCancelled(ALICE, 0),
+        ));
+        //检查存储是否清除
+        assert_eq!(KittiesModule::kitties(0), None);
+        assert_eq!(KittiesModule::owner(0), None);
+        assert_eq!(KittiesModule::kitty_prices(0), None);
+        assert_eq!(KittiesModule::sire_prices(0), None);
+        assert!(!KittiesModule::is_owned_by(&ALICE, 0));
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), 0);
+        //检查质押是否解除
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 10_000);
+        //销毁后无法再次销毁
+        assert_noop!(
+            KittiesModule::burn(Origin::signed(ALICE), 0, 0),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn can_burn_failed_not_owner() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_noop!(
+            KittiesModule::burn(Origin::signed(BOB), 0, 0),
+            Error::<Test>::NotOwner
+        );
+    });
+}
+
+#[test]
+fn can_burn_release_offers() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+        assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 3_000, 20));
+        //检查报价数量上限过小，是否返回正确错误
+        assert_noop!(
+            KittiesModule::burn(Origin::signed(ALICE), 0, 1),
+            Error::<Test>::OfferWitnessTooLow
+        );
+
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 0, 2));
+        System::assert_has_event(mock::Event::KittiesModule(crate::Event::OfferCancelled(
+            BOB, 0,
+        )));
+        System::assert_has_event(mock::Event::KittiesModule(crate::Event::OfferCancelled(3, 0)));
+        //检查报价是否移除，质押是否退还
+        assert_eq!(KittiesModule::offers(0, BOB), None);
+        assert_eq!(KittiesModule::offers(0, 3), None);
+        assert!(!OfferExpiries::<Test>::contains_key(10, (0, BOB)));
+        assert!(!OfferExpiries::<Test>::contains_key(20, (0, 3)));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::reserved_balance(3), 0);
+    });
+}
+
+#[test]
+fn can_burn_keep_lineage() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Female);
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+        let sire = KittiesModule::kitties(1).unwrap();
+
+        run_to_block(3);
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 1, 0));
+        //后代的父母仍可通过墓碑查询
+        assert_eq!(KittiesModule::kitties(2).unwrap().parents, Some((0, 1)));
+        assert_eq!(KittiesModule::lineage_of(1), Some(sire.clone()));
+        let tombstone = KittiesModule::tombstones(1).unwrap();
+        assert_eq!(tombstone.kitty, sire);
+        assert_eq!(tombstone.owner, ALICE);
+        assert_eq!(tombstone.burned_at, 3);
+        //已销毁的Kitty仍可通过 kitty_info 查询
+        let info = KittiesModule::kitty_info(1).unwrap();
+        assert_eq!(info.dna, sire.dna);
+        assert_eq!(info.owner, ALICE);
+        assert_eq!(info.price, None);
+        assert_eq!(info.burned_at, Some(3));
+        //已销毁的Kitty无法繁殖
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(ALICE), 0, 1),
+            Error::<Test>::InvalidKittyIndex
+        );
+    });
+}
+
+#[test]
+fn can_approve_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查非拥有者授权，是否返回正确错误
+        assert_noop!(
+            KittiesModule::approve(Origin::signed(BOB), 0, Some(3)),
+            Error::<Test>::NotOwner
+        );
+        //检查授权给拥有者本人，是否返回正确错误
+        assert_noop!(
+            KittiesModule::approve(Origin::signed(ALICE), 0, Some(ALICE)),
+            Error::<Test>::AlreadyOwned
+        );
+
+        assert_ok!(KittiesModule::approve(Origin::signed(ALICE), 0, Some(BOB)));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyApproved(
+            ALICE, BOB, 0,
+        )));
+        assert_eq!(KittiesModule::approvals(0), Some(BOB));
+
+        //被授权账户转移给第三方，需第三方确认
+        assert_ok!(KittiesModule::transfer_from(Origin::signed(BOB), ALICE, 3, 0));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyTransferOffered(ALICE, 3, 0, 1 + TransferExpiry::get()),
+        ));
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_eq!(Balances::reserved_balance(3), 0);
+
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(3), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyTransfer(
+            ALICE, 3, 0,
+        )));
+        System::assert_has_event(mock::Event::KittiesModule(
+            crate::Event::KittyApprovalCleared(ALICE, 0),
+        ));
+        assert_eq!(KittiesModule::owner(0), Some(3));
+        assert_eq!(KittiesModule::approvals(0), None);
+        //质押转移到新拥有者
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::reserved_balance(3), KITTY_RESERVE);
+
+        //授权已清除，无法再次转移
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), 3, BOB, 0),
+            Error::<Test>::NotApproved
+        );
+    });
+}
+
+#[test]
+fn can_set_approval_for_all_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(ALICE, Gender::Female);
+        assert_noop!(
+            KittiesModule::set_approval_for_all(Origin::signed(ALICE), ALICE, true),
+            Error::<Test>::AlreadyOwned
+        );
+
+        assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(ALICE), BOB, true));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::ApprovalForAll(
+            ALICE, BOB, true,
+        )));
+        assert!(KittiesModule::is_operator(&ALICE, &BOB));
+
+        //操作者可转移拥有者的任意Kitty，并可为其授权
+        assert_ok!(KittiesModule::transfer_from(Origin::signed(BOB), ALICE, BOB, 0));
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_ok!(KittiesModule::approve(Origin::signed(BOB), 1, Some(3)));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyApproved(
+            ALICE, 3, 1,
+        )));
+
+        //撤销操作者
+        assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(ALICE), BOB, false));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::ApprovalForAll(
+            ALICE, BOB, false,
+        )));
+        assert!(!KittiesModule::is_operator(&ALICE, &BOB));
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), ALICE, BOB, 1),
+            Error::<Test>::NotApproved
+        );
+    });
+}
+
+#[test]
+fn can_transfer_from_failed() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查 from 非拥有者，是否返回正确错误
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), BOB, 3, 0),
+            Error::<Test>::NotOwner
+        );
+        //检查未授权，是否返回正确错误
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), ALICE, BOB, 0),
+            Error::<Test>::NotApproved
+        );
+        //检查新拥有者余额不足，是否返回正确错误
+        assert_ok!(KittiesModule::approve(Origin::signed(ALICE), 0, Some(NOBODY)));
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(NOBODY), ALICE, NOBODY, 0),
+            Error::<Test>::MoneyNotEnough
+        );
+    });
+}
+
+#[test]
+fn can_clear_approval_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查没有授权时清除，是否返回正确错误
+        assert_noop!(
+            KittiesModule::approve(Origin::signed(ALICE), 0, None),
+            Error::<Test>::NotApproved
+        );
+
+        assert_ok!(KittiesModule::approve(Origin::signed(ALICE), 0, Some(BOB)));
+        //检查非拥有者清除，是否返回正确错误
+        assert_noop!(
+            KittiesModule::approve(Origin::signed(BOB), 0, None),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(KittiesModule::approve(Origin::signed(ALICE), 0, None));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyApprovalCleared(ALICE, 0),
+        ));
+        assert_eq!(KittiesModule::approvals(0), None);
+
+        //授权已清除，无法转移
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), ALICE, BOB, 0),
+            Error::<Test>::NotApproved
+        );
+    });
+}
+
+#[test]
+fn can_approval_cleared_on_transfer() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::approve(Origin::signed(ALICE), 0, Some(3)));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        assert_eq!(KittiesModule::approvals(0), None);
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(3), BOB, 3, 0),
+            Error::<Test>::NotApproved
+        );
+    });
+}
+
+#[test]
+fn can_create_auction_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)));
+        //检查非拥有者发起拍卖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::create_auction(Origin::signed(BOB), 0, 1_000, 10),
+            Error::<Test>::NotOwner
+        );
+        //检查拍卖时长，是否返回正确错误
+        assert_noop!(
+            KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 0),
+            Error::<Test>::InvalidAuctionDuration
+        );
+        assert_noop!(
+            KittiesModule::create_auction(
+                Origin::signed(ALICE),
+                0,
+                1_000,
+                MaxAuctionDuration::get() + 1
+            ),
+            Error::<Test>::InvalidAuctionDuration
+        );
+
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionCreated(
+            ALICE, 0, 1_000, 11,
+        )));
+        //挂售被取消
+        System::assert_has_event(mock::Event::KittiesModule(
+            crate::Event::KittySaleCancelled(ALICE, 0),
+        ));
+        assert_eq!(KittiesModule::kitty_prices(0), None);
+
+        //拍卖中无法重复拍卖、挂售、转移或销毁
+        assert_noop!(
+            KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10),
+            Error::<Test>::KittyInAuction
+        );
+        assert_noop!(
+            KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)),
+            Error::<Test>::KittyInAuction
+        );
+        assert_noop!(
+            KittiesModule::transfer(Origin::signed(ALICE), BOB, 0),
+            Error::<Test>::KittyInAuction
+        );
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(ALICE), ALICE, BOB, 0),
+            Error::<Test>::KittyInAuction
+        );
+        assert_noop!(
+            KittiesModule::burn(Origin::signed(ALICE), 0, 0),
+            Error::<Test>::KittyInAuction
+        );
+    });
+}
+
+#[test]
+fn can_bid_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(BOB), 0, 1_000),
+            Error::<Test>::NotInAuction
+        );
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+
+        //检查出价低于最低成交价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(BOB), 0, 500),
+            Error::<Test>::BidTooLow
+        );
+        //检查卖方出价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(ALICE), 0, 1_000),
+            Error::<Test>::AlreadyOwned
+        );
+        //检查余额不足，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(NOBODY), 0, 1_000),
+            Error::<Test>::MoneyNotEnough
+        );
+
+        //出价金额及Kitty质押被质押
+        assert_ok!(KittiesModule::bid(Origin::signed(BOB), 0, 1_000));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::BidPlaced(
+            BOB, 0, 1_000,
+        )));
+        assert_eq!(Balances::reserved_balance(BOB), 1_000 + KITTY_RESERVE);
+
+        //检查出价未超过最高出价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(3), 0, 1_000),
+            Error::<Test>::BidTooLow
+        );
+
+        //被超过的出价退还
+        assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 1_500));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), 20_000);
+        assert_eq!(Balances::reserved_balance(3), 1_500 + KITTY_RESERVE);
+        assert_eq!(KittiesModule::auctions(0).unwrap().highest_bid, Some((3, 1_500)));
+    });
+}
+
+#[test]
+fn can_auction_settle_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+        assert_ok!(KittiesModule::bid(Origin::signed(BOB), 0, 1_000));
+        assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 1_500));
+
+        run_to_block(10);
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+
+        //结束区块结算
+        run_to_block(11);
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionSettled(
+            ALICE, 3, 0, 1_500, 75, 0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(3));
+        assert!(KittiesModule::is_owned_by(&3, 0));
+        assert_eq!(KittiesModule::auctions(0), None);
+        //卖方收到扣除市场手续费后的出价并解除质押，卖方即创建者时无版税，买方保留Kitty质押
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 1_500 - 75);
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), 1_000 + 75);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(3), 30_000 - 1_500 - KITTY_RESERVE);
+        assert_eq!(Balances::reserved_balance(3), KITTY_RESERVE);
+
+        assert_noop!(
+            KittiesModule::bid(Origin::signed(BOB), 0, 2_000),
+            Error::<Test>::NotInAuction
+        );
+    });
+}
+
+#[test]
+fn can_auction_settle_carry_over() {
+    new_test_ext().execute_with(|| {
+        for kitty_id in 0..3 {
+            create_kitty(ALICE, Gender::Male);
+            assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), kitty_id, 1_000, 10));
+            assert_ok!(KittiesModule::bid(Origin::signed(BOB), kitty_id, 1_000));
+        }
+
+        //每个区块最多结算 MaxAuctionsPerBlock 个拍卖
+        run_to_block(11);
+        assert_eq!(KittiesModule::owned_kitties_count(BOB), MaxAuctionsPerBlock::get());
+        assert_eq!(Auctions::<Test>::iter().count(), 1);
+
+        //未结算的拍卖顺延至下一区块
+        run_to_block(12);
+        assert_eq!(KittiesModule::owned_kitties_count(BOB), 3);
+        assert_eq!(Auctions::<Test>::iter().count(), 0);
+        assert_eq!(AuctionSettlementCursor::<Test>::get(), 13);
+    });
+}
+
+#[test]
+fn can_auction_settle_pay_royalty() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        assert_ok!(KittiesModule::create_auction(Origin::signed(BOB), 0, 1_000, 10));
+        assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 2_000));
+
+        run_to_block(11);
+        //成交价扣除市场手续费及创建者版税后支付给卖方
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionSettled(
+            BOB, 3, 0, 2_000, 100, 200,
+        )));
+        assert_eq!(Balances::free_balance(BOB), 20_000 + 2_000 - 100 - 200);
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 200);
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), 1_000 + 100);
+        assert_eq!(Balances::free_balance(3), 30_000 - 2_000 - KITTY_RESERVE);
+    });
+}
+
+#[test]
+fn can_auction_settlement_fail_on_short_reserve() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+        assert_ok!(KittiesModule::bid(Origin::signed(BOB), 0, 1_500));
+        //出价质押被部分解除，不足以支付出价
+        Balances::unreserve_named(&RESERVE_ID, &BOB, 2_000);
+
+        run_to_block(11);
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::AuctionSettlementFailed(ALICE, BOB, 0),
+        ));
+        //卖方未收到任何部分，Kitty仍归卖方
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_eq!(KittiesModule::auctions(0), None);
+        assert_eq!(Balances::free_balance(ALICE), 10_000 - KITTY_RESERVE);
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
+        //剩余出价质押退还
+        assert_eq!(Balances::free_balance(BOB), 20_000);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+    });
+}
+
+#[test]
+fn can_auction_expire_without_bids() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+
+        run_to_block(11);
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionExpired(
+            ALICE, 0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_eq!(KittiesModule::auctions(0), None);
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
+        //拍卖结束后可再次挂售
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)));
+    });
+}
+
+#[test]
+fn can_auction_extend_on_late_bid() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+
+        //距结束较远的出价不延长
+        assert_ok!(KittiesModule::bid(Origin::signed(BOB), 0, 1_000));
+        assert_eq!(KittiesModule::auctions(0).unwrap().end_block, 11);
+
+        //结束前 AuctionExtension 区块内的出价延长拍卖
+        run_to_block(10);
+        assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 1_500));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionExtended(
+            0,
+            10 + AuctionExtension::get(),
+        )));
+        assert_eq!(
+            KittiesModule::auctions(0).unwrap().end_block,
+            10 + AuctionExtension::get()
+        );
+
+        //原结束区块不再结算
+        run_to_block(11);
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_ok!(KittiesModule::bid(Origin::signed(BOB), 0, 2_000));
+
+        run_to_block(20);
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+    });
+}
+
+#[test]
+fn can_create_dutch_auction_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查非拥有者发起拍卖，是否返回正确错误
+        assert_noop!(
+            KittiesModule::create_dutch_auction(Origin::signed(BOB), 0, 5_000, 1_000, 10),
+            Error::<Test>::NotOwner
+        );
+        //检查起始价低于结束价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::create_dutch_auction(Origin::signed(ALICE), 0, 1_000, 5_000, 10),
+            Error::<Test>::InvalidPriceRange
+        );
+        assert_noop!(
+            KittiesModule::create_dutch_auction(Origin::signed(ALICE), 0, 5_000, 1_000, 0),
+            Error::<Test>::InvalidAuctionDuration
+        );
+
+        assert_ok!(KittiesModule::create_dutch_auction(
+            Origin::signed(ALICE),
+            0,
+            5_000,
+            1_000,
+            10
+        ));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::DutchAuctionCreated(ALICE, 0, 5_000, 1_000, 11),
+        ));
+        assert_eq!(KittiesModule::kitty_prices(0), Some(5_000));
+
+        //价格随区块线性下降，到期后保持结束价
+        assert_eq!(KittiesModule::current_price(0), Some((5_000, None)));
+        run_to_block(6);
+        assert_eq!(KittiesModule::current_price(0), Some((3_000, None)));
+        assert_eq!(KittiesModule::kitty_info(0).unwrap().price, Some(3_000));
+        assert_eq!(KittiesModule::kitties_for_sale(None, 10), vec![(0, 3_000, None)]);
+        run_to_block(11);
+        assert_eq!(KittiesModule::current_price(0), Some((1_000, None)));
+        run_to_block(20);
+        assert_eq!(KittiesModule::current_price(0), Some((1_000, None)));
+    });
+}
+
+#[test]
+fn can_buy_dutch_auction_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_dutch_auction(
+            Origin::signed(ALICE),
+            0,
+            5_000,
+            1_000,
+            10
+        ));
+
+        //按当前区块价格购买
+        run_to_block(6);
+        assert_ok!(KittiesModule::buy(Origin::signed(BOB), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            BOB,
+            0,
+            3_000,
+            150,
+            0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_eq!(KittiesModule::dutch_auctions(0), None);
+        assert_eq!(KittiesModule::current_price(0), None);
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 3_000 - 150);
+        assert_eq!(Balances::free_balance(BOB), 20_000 - 3_000 - KITTY_RESERVE);
+    });
+}
+
+#[test]
+fn can_dutch_auction_replaced_by_sale() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::create_dutch_auction(
+            Origin::signed(ALICE),
+            0,
+            5_000,
+            1_000,
+            10
+        ));
+
+        //固定价格挂售替换降价计划
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)));
+        assert_eq!(KittiesModule::dutch_auctions(0), None);
+        run_to_block(6);
+        assert_eq!(KittiesModule::current_price(0), Some((2_000, None)));
+
+        //取消挂售同时移除降价计划
+        assert_ok!(KittiesModule::create_dutch_auction(
+            Origin::signed(ALICE),
+            0,
+            5_000,
+            1_000,
+            10
+        ));
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, None));
+        assert_eq!(KittiesModule::dutch_auctions(0), None);
+        assert_noop!(
+            KittiesModule::buy(Origin::signed(BOB), 0),
+            Error::<Test>::NotForSale
+        );
+
+        //英式拍卖中无法发起荷兰式拍卖
+        assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), 0, 1_000, 10));
+        assert_noop!(
+            KittiesModule::create_dutch_auction(Origin::signed(ALICE), 0, 5_000, 1_000, 10),
+            Error::<Test>::KittyInAuction
+        );
+    });
+}
+
+#[test]
+fn can_make_offer_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查Kitty不存在，是否返回正确错误
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(BOB), 5, 2_000, 10),
+            Error::<Test>::InvalidKittyIndex
+        );
+        //检查拥有者报价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(ALICE), 0, 2_000, 10),
+            Error::<Test>::AlreadyOwned
+        );
+        //检查失效区块，是否返回正确错误
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 0),
+            Error::<Test>::InvalidOfferExpiry
+        );
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 2 + MaxOfferDuration::get()),
+            Error::<Test>::InvalidOfferExpiry
+        );
+        //检查余额不足，是否返回正确错误
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(NOBODY), 0, 2_000, 10),
+            Error::<Test>::MoneyNotEnough
+        );
+
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::OfferMade(
+            BOB, 0, 2_000, 10,
+        )));
+        assert_eq!(Balances::reserved_balance(BOB), 2_000 + KITTY_RESERVE);
+
+        //新报价替换旧报价
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 3_000, 20));
+        assert_eq!(Balances::reserved_balance(BOB), 3_000 + KITTY_RESERVE);
+        assert_eq!(
+            KittiesModule::offers(0, BOB),
+            Some(Offer {
+                amount: 3_000,
+                expires_at: 20,
+                deposit: KITTY_RESERVE,
+            })
+        );
+    });
+}
+
+#[test]
+fn can_accept_offer_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+        assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 1_500, 10));
+        //检查非拥有者接受报价，是否返回正确错误
+        assert_noop!(
+            KittiesModule::accept_offer(Origin::signed(BOB), 0, BOB),
+            Error::<Test>::NotOwner
+        );
+        //检查报价不存在，是否返回正确错误
+        assert_noop!(
+            KittiesModule::accept_offer(Origin::signed(ALICE), 0, NOBODY),
+            Error::<Test>::NoOffer
+        );
+
+        assert_ok!(KittiesModule::accept_offer(Origin::signed(ALICE), 0, BOB));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::OfferAccepted(
+            ALICE, BOB, 0, 2_000, 100, 0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_eq!(KittiesModule::offers(0, BOB), None);
+        //拥有者收到扣除市场手续费后的报价并解除质押，买方保留Kitty质押
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 2_000 - 100);
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), 1_000 + 100);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(BOB), 20_000 - 2_000 - KITTY_RESERVE);
+        assert_eq!(Balances::reserved_balance(BOB), KITTY_RESERVE);
+
+        //其他报价保留，新拥有者可接受，版税支付给创建者
+        assert_ok!(KittiesModule::accept_offer(Origin::signed(BOB), 0, 3));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::OfferAccepted(
+            BOB, 3, 0, 1_500, 75, 150,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(3));
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 2_000 - 100 + 150);
+        assert_eq!(
+            Balances::free_balance(BOB),
+            20_000 - 2_000 - KITTY_RESERVE + 1_500 - 75 - 150 + KITTY_RESERVE
+        );
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), 1_000 + 100 + 75);
+    });
+}
+
+#[test]
+fn can_make_offer_keep_old_on_failed_replace() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+
+        //检查替换报价余额不足，是否返回正确错误并保留旧报价
+        assert_noop!(
+            KittiesModule::make_offer(Origin::signed(BOB), 0, 20_000, 20),
+            Error::<Test>::MoneyNotEnough
+        );
+        let offer = KittiesModule::offers(0, BOB).unwrap();
+        assert_eq!(offer.amount, 2_000);
+        assert_eq!(offer.expires_at, 10);
+        assert!(OfferExpiries::<Test>::contains_key(10, (0, BOB)));
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &BOB),
+            2_000 + KITTY_RESERVE
+        );
+    });
+}
+
+#[test]
+fn can_accept_offer_failed_on_short_reserve() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+        //报价质押被部分解除，不足以支付报价
+        Balances::unreserve_named(&RESERVE_ID, &BOB, 2_500);
+
+        //检查质押不足时接受报价，是否返回正确错误且不支付任何部分
+        assert_noop!(
+            KittiesModule::accept_offer(Origin::signed(ALICE), 0, BOB),
+            Error::<Test>::MoneyNotEnough
+        );
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &BOB), 500);
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+    });
+}
+
+#[test]
+fn can_cancel_offer_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_noop!(
+            KittiesModule::cancel_offer(Origin::signed(BOB), 0),
+            Error::<Test>::NoOffer
+        );
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 10));
+
+        assert_ok!(KittiesModule::cancel_offer(Origin::signed(BOB), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::OfferCancelled(
+            BOB, 0,
+        )));
+        assert_eq!(KittiesModule::offers(0, BOB), None);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_noop!(
+            KittiesModule::accept_offer(Origin::signed(ALICE), 0, BOB),
+            Error::<Test>::NoOffer
+        );
+    });
+}
+
+#[test]
+fn can_expired_offer_cleaned_on_idle() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), 0, 2_000, 5));
+        assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 1_500, 8));
+
+        //检查接受已失效的报价，是否返回正确错误
+        run_to_block(6);
+        assert_noop!(
+            KittiesModule::accept_offer(Origin::signed(ALICE), 0, BOB),
+            Error::<Test>::OfferExpired
+        );
+
+        //失效后的下一区块清理并退还质押
+        run_to_block(7);
+        System::assert_has_event(mock::Event::KittiesModule(crate::Event::OfferExpired(
+            BOB, 0,
+        )));
+        assert_eq!(KittiesModule::offers(0, BOB), None);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), 20_000);
+        assert!(KittiesModule::offers(0, 3).is_some());
+
+        run_to_block(10);
+        assert_eq!(KittiesModule::offers(0, 3), None);
+        assert_eq!(Balances::reserved_balance(3), 0);
+    });
+}
+
+#[test]
+fn can_buy_pay_fee_and_royalty() {
+    new_test_ext().execute_with(|| {
+        //ALICE 创建后出售给 BOB，BOB 再出售给账户3
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(2_000)));
+        assert_ok!(KittiesModule::buy(Origin::signed(BOB), 0));
+        let alice_balance = Balances::free_balance(ALICE);
+        let bob_balance = Balances::free_balance(BOB);
+        let fee_balance = Balances::free_balance(FEE_ACCOUNT);
+
+        assert_ok!(KittiesModule::sale(Origin::signed(BOB), 0, Some(10_000)));
+        assert_ok!(KittiesModule::buy(Origin::signed(3), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            3,
+            0,
+            10_000,
+            500,
+            1_000,
+        )));
+        //手续费 5%，版税 10% 支付给创建者，余下支付给卖方
+        assert_eq!(Balances::free_balance(FEE_ACCOUNT), fee_balance + 500);
+        assert_eq!(Balances::free_balance(ALICE), alice_balance + 1_000);
+        assert_eq!(
+            Balances::free_balance(BOB),
+            bob_balance + 10_000 - 500 - 1_000 + KITTY_RESERVE
+        );
+        assert_eq!(
+            Balances::free_balance(3),
+            30_000 - 10_000 - KITTY_RESERVE
+        );
+    });
+}
+
+#[test]
+fn can_buy_royalty_to_seller_when_creator_gone() {
+    new_test_ext().execute_with(|| {
+        //创建者转出全部余额后账户被清除
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        assert_ok!(Balances::transfer(Origin::signed(ALICE), 3, 10_000));
+        assert!(!System::account_exists(&ALICE));
+
+        assert_ok!(KittiesModule::sale(Origin::signed(BOB), 0, Some(10_000)));
+        assert_ok!(KittiesModule::buy(Origin::signed(3), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            3,
+            0,
+            10_000,
+            500,
+            0,
+        )));
+        //版税归卖方，卖方解除质押
+        assert_eq!(Balances::free_balance(BOB), 20_000 + 10_000 - 500);
+    });
+}
+
+const ASSET_ID: u32 = 7;
+
+// 创建资产并为 BOB 及账户3发放资产
+fn create_asset() {
+    assert_ok!(Assets::force_create(Origin::root(), ASSET_ID, ALICE, true, 1));
+    assert_ok!(Assets::mint(Origin::signed(ALICE), ASSET_ID, BOB, 10_000));
+    assert_ok!(Assets::mint(Origin::signed(ALICE), ASSET_ID, 3, 10_000));
+}
+
+#[test]
+fn can_sale_in_asset_work() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //检查非拥有者挂售，是否返回正确错误
+        assert_noop!(
+            KittiesModule::sale_in_asset(Origin::signed(BOB), 0, ASSET_ID, 2_000),
+            Error::<Test>::NotOwner
+        );
+        //检查价格超出资产数量范围，是否返回正确错误
+        assert_noop!(
+            KittiesModule::sale_in_asset(Origin::signed(ALICE), 0, ASSET_ID, u64::MAX as u128 + 1),
+            Error::<Test>::PriceOverflow
+        );
+
+        assert_ok!(KittiesModule::sale_in_asset(Origin::signed(ALICE), 0, ASSET_ID, 2_000));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::KittyForSaleInAsset(ALICE, 0, ASSET_ID, 2_000),
+        ));
+        assert_eq!(KittiesModule::kitty_prices(0), Some(2_000));
+        assert_eq!(KittiesModule::kitty_price_assets(0), Some(ASSET_ID));
+
+        //以 T::Currency 重新挂售
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(3_000)));
+        assert_eq!(KittiesModule::kitty_price_assets(0), None);
+
+        //取消挂售同时移除资产
+        assert_ok!(KittiesModule::sale_in_asset(Origin::signed(ALICE), 0, ASSET_ID, 2_000));
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, None));
+        assert_eq!(KittiesModule::kitty_price_assets(0), None);
+    });
+}
+
+#[test]
+fn can_buy_in_asset_work() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale_in_asset(Origin::signed(ALICE), 0, ASSET_ID, 2_000));
+        //查询时返回计价资产
+        assert_eq!(KittiesModule::current_price(0), Some((2_000, Some(ASSET_ID))));
+        assert_eq!(KittiesModule::kitty_info(0).unwrap().price_asset, Some(ASSET_ID));
+        assert_eq!(
+            KittiesModule::kitties_for_sale(None, 10),
+            vec![(0, 2_000, Some(ASSET_ID))]
+        );
+
+        assert_ok!(KittiesModule::buy(Origin::signed(BOB), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            BOB,
+            0,
+            2_000,
+            100,
+            0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_eq!(KittiesModule::kitty_price_assets(0), None);
+        //以资产结算，市场手续费以资产支付给 MarketplaceAccount，Kitty质押仍使用 T::Currency
+        assert_eq!(Assets::balance(ASSET_ID, ALICE), 2_000 - 100);
+        assert_eq!(Assets::balance(ASSET_ID, BOB), 10_000 - 2_000);
+        assert_eq!(Assets::balance(ASSET_ID, FEE_ACCOUNT), 100);
+        assert_eq!(Assets::total_supply(ASSET_ID), 20_000);
+        assert_eq!(Balances::free_balance(ALICE), 10_000);
+        assert_eq!(Balances::free_balance(BOB), 20_000 - KITTY_RESERVE);
+
+        //转售时支付版税给创建者
+        assert_ok!(KittiesModule::sale_in_asset(Origin::signed(BOB), 0, ASSET_ID, 5_000));
+        assert_ok!(KittiesModule::buy(Origin::signed(3), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            3,
+            0,
+            5_000,
+            250,
+            500,
+        )));
+        assert_eq!(Assets::balance(ASSET_ID, ALICE), 1_900 + 500);
+        assert_eq!(Assets::balance(ASSET_ID, BOB), 8_000 + 4_250);
+        assert_eq!(Assets::balance(ASSET_ID, 3), 10_000 - 5_000);
+        assert_eq!(Assets::balance(ASSET_ID, FEE_ACCOUNT), 100 + 250);
+        assert_eq!(Assets::total_supply(ASSET_ID), 20_000);
+    });
+}
+
+#[test]
+fn can_buy_in_asset_failed_not_enough_asset() {
+    new_test_ext().execute_with(|| {
+        create_asset();
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale_in_asset(Origin::signed(ALICE), 0, ASSET_ID, 20_000));
+
+        //资产不足时购买失败且不质押
+        assert_noop!(
+            KittiesModule::buy(Origin::signed(BOB), 0),
+            pallet_assets::Error::<Test>::BalanceLow
+        );
+    });
+}
+
+#[test]
+fn can_deposit_follow_reserve_change() {
+    new_test_ext().execute_with(|| {
+        create_kitty(ALICE, Gender::Male);
+        //修改质押数量后创建的Kitty按新数量质押
+        assert_ok!(KittiesModule::set_kitty_reserve(Origin::root(), 2_000));
+        create_kitty(ALICE, Gender::Female);
+        assert_eq!(KittiesModule::kitty_deposits(0), 1_000);
+        assert_eq!(KittiesModule::kitty_deposits(1), 2_000);
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &ALICE), 3_000);
+
+        //销毁时按记录解除质押
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 0, 0));
+        assert_eq!(KittiesModule::kitty_deposits(0), 0);
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &ALICE), 2_000);
+
+        //转移时原拥有者按记录解除质押，新拥有者按当前数量质押
+        assert_ok!(KittiesModule::set_kitty_reserve(Origin::root(), 1_500));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 1));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 1));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &BOB), 1_500);
+        assert_eq!(KittiesModule::kitty_deposits(1), 1_500);
+    });
+}
+
+#[test]
+fn can_deposit_not_mixed_with_other_reserves() {
+    new_test_ext().execute_with(|| {
+        //其他模块的匿名质押不受影响
+        assert_ok!(Balances::reserve(&ALICE, 500));
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 0, 0));
+        assert_eq!(Balances::reserved_balance(ALICE), 500);
+    });
+}
+
+#[test]
+fn can_migrate_to_v3_work() {
+    new_test_ext().execute_with(|| {
+        //写入V2格式的存储及匿名质押
+        frame_support::storage::unhashed::put(&Owner::<Test>::hashed_key_for(0), &Some(ALICE));
+        assert_ok!(Balances::reserve(&ALICE, KITTY_RESERVE));
+        frame_support::storage::unhashed::put(
+            &Offers::<Test>::hashed_key_for(0, BOB),
+            &(2_000u128, 10u64),
+        );
+        assert_ok!(Balances::reserve(&BOB, 2_000 + KITTY_RESERVE));
+        frame_support::storage::unhashed::put(
+            &Auctions::<Test>::hashed_key_for(1),
+            &(ALICE, 1_000u128, 11u64, Some((3u64, 1_500u128))),
+        );
+        assert_ok!(Balances::reserve(&3, 1_500 + KITTY_RESERVE));
+        crate::pallet::StorageVersion::<Test>::put(Releases::V2);
+
+        KittiesModule::on_runtime_upgrade();
+
+        assert_eq!(KittiesModule::kitty_deposits(0), KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &ALICE),
+            KITTY_RESERVE
+        );
+        assert_eq!(KittiesModule::offers(0, BOB).unwrap().deposit, KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &BOB),
+            2_000 + KITTY_RESERVE
+        );
+        assert_eq!(KittiesModule::auctions(1).unwrap().bid_deposit, KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &3),
+            1_500 + KITTY_RESERVE
+        );
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V5);
+    });
+}
+
+#[test]
+fn can_migrate_to_v3_keep_other_reserves() {
+    new_test_ext().execute_with(|| {
+        //运行时已修改质押数量，账户还持有其他模块的匿名质押
+        assert_ok!(KittiesModule::set_kitty_reserve(Origin::root(), 5_000));
+        frame_support::storage::unhashed::put(&Owner::<Test>::hashed_key_for(0), &Some(ALICE));
+        frame_support::storage::unhashed::put(&Owner::<Test>::hashed_key_for(1), &Some(ALICE));
+        assert_ok!(Balances::reserve(&ALICE, 2 * KITTY_RESERVE + 3_000));
+        frame_support::storage::unhashed::put(
+            &Offers::<Test>::hashed_key_for(0, BOB),
+            &(2_000u128, 10u64),
+        );
+        assert_ok!(Balances::reserve(&BOB, 2_000 + KITTY_RESERVE + 4_000));
+        crate::pallet::StorageVersion::<Test>::put(Releases::V2);
+
+        KittiesModule::on_runtime_upgrade();
+
+        //按V2时的质押数量转换，其他匿名质押保持不变
+        assert_eq!(KittiesModule::kitty_deposits(0), KITTY_RESERVE);
+        assert_eq!(KittiesModule::kitty_deposits(1), KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &ALICE),
+            2 * KITTY_RESERVE
+        );
+        assert_eq!(Balances::reserved_balance(&ALICE), 2 * KITTY_RESERVE + 3_000);
+        assert_eq!(KittiesModule::offers(0, BOB).unwrap().deposit, KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &BOB),
+            2_000 + KITTY_RESERVE
+        );
+        assert_eq!(Balances::reserved_balance(&BOB), 2_000 + KITTY_RESERVE + 4_000);
+    });
+}
+
+#[test]
+fn can_migrate_to_v4_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        let kitty = KittiesModule::kitties(0).unwrap();
+
+        //写入V3格式的存储，即以 ValueQuery 保存的 Option 值
+        frame_support::storage::unhashed::put(
+            &Kitties::<Test>::hashed_key_for(0),
+            &Some(kitty.clone()),
+        );
+        frame_support::storage::unhashed::put(&Owner::<Test>::hashed_key_for(0), &Some(ALICE));
+        frame_support::storage::unhashed::put(
+            &KittyPrices::<Test>::hashed_key_for(0),
+            &Some(8_000u128),
+        );
+        frame_support::storage::unhashed::put(
+            &KittyPrices::<Test>::hashed_key_for(1),
+            &None::<u128>,
+        );
+        crate::pallet::StorageVersion::<Test>::put(Releases::V3);
+
+        KittiesModule::on_runtime_upgrade();
+
+        assert_eq!(KittiesModule::kitties(0).unwrap().dna, kitty.dna);
+        assert_eq!(KittiesModule::owner(0), Some(ALICE));
+        assert_eq!(KittiesModule::kitty_prices(0), Some(8_000));
+        assert!(!KittyPrices::<Test>::contains_key(1));
+        assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V5);
+    });
+}
+
+#[test]
+fn can_migrate_to_v5_work() {
+    new_test_ext().execute_with(|| {
+        //写入没有拥有索引的Kitty
+        Owner::<Test>::insert(0, ALICE);
+        Owner::<Test>::insert(1, BOB);
+        Owner::<Test>::insert(2, ALICE);
+        crate::pallet::StorageVersion::<Test>::put(Releases::V4);
+
+        KittiesModule::on_runtime_upgrade();
+
+        assert_eq!(KittiesModule::kitties_of(&ALICE), vec![0, 2]);
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), 2);
+        assert_eq!(KittiesModule::kitties_of(&BOB), vec![1]);
+        assert_eq!(KittiesModule::owned_kitties_count(BOB), 1);
+        assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V5);
+    });
+}
+
+#[test]
+fn can_migrate_from_v1_work() {
+    new_test_ext().execute_with(|| {
+        //写入V1格式的Kitty、拥有者及匿名质押，没有拥有索引
+        for (kitty_id, owner) in [(0u32, ALICE), (1, BOB), (2, ALICE)].iter() {
+            frame_support::storage::unhashed::put(
+                &Kitties::<Test>::hashed_key_for(kitty_id),
+                &Some([*kitty_id as u8; 16]),
+            );
+            frame_support::storage::unhashed::put(
+                &Owner::<Test>::hashed_key_for(kitty_id),
+                &Some(*owner),
+            );
+            assert_ok!(Balances::reserve(owner, KITTY_RESERVE));
+        }
+        KittiesCount::<Test>::put(3);
+        crate::pallet::StorageVersion::<Test>::put(Releases::V1);
+        #[cfg(feature = "try-runtime")]
+        assert_ok!(crate::migrations::pre_upgrade::<Test>());
+
+        KittiesModule::on_runtime_upgrade();
+
+        assert_eq!(KittiesModule::kitties(2).unwrap().dna, [2u8; 16]);
+        assert_eq!(KittiesModule::owner(1), Some(BOB));
+        assert_eq!(KittiesModule::kitties_of(&ALICE), vec![0, 2]);
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), 2);
+        assert_eq!(KittiesModule::kitties_of(&BOB), vec![1]);
+        assert_eq!(KittiesModule::owned_kitties_count(BOB), 1);
+        assert_eq!(KittiesModule::kitty_deposits(0), KITTY_RESERVE);
+        assert_eq!(
+            Balances::reserved_balance_named(&RESERVE_ID, &ALICE),
+            2 * KITTY_RESERVE
+        );
+        assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V5);
+        #[cfg(feature = "try-runtime")]
+        assert_ok!(crate::migrations::post_upgrade::<Test>());
+    });
+}
+
+#[test]
+fn can_set_kitty_reserve_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(KittiesModule::kitty_reserve(), KITTY_RESERVE);
+        //检查非 ForceOrigin 调用，是否返回正确错误
+        assert_noop!(
+            KittiesModule::set_kitty_reserve(Origin::signed(ALICE), 2_000),
+            BadOrigin
+        );
+
+        assert_ok!(KittiesModule::set_kitty_reserve(Origin::root(), 2_000));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittyReserveSet(
+            2_000,
+        )));
+        assert_eq!(KittiesModule::kitty_reserve(), 2_000);
+
+        create_kitty(ALICE, Gender::Male);
+        assert_eq!(Balances::reserved_balance(ALICE), 2_000);
+    });
+}
+
+#[test]
+fn can_set_fee_rates_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(KittiesModule::marketplace_fee(), MarketplaceFee::get());
+        assert_eq!(KittiesModule::creator_royalty(), CreatorRoyalty::get());
+        assert_noop!(
+            KittiesModule::set_marketplace_fee(Origin::signed(ALICE), Permill::from_percent(1)),
+            BadOrigin
+        );
+        assert_noop!(
+            KittiesModule::set_creator_royalty(Origin::signed(ALICE), Permill::from_percent(1)),
+            BadOrigin
+        );
+        //检查手续费与版税之和超过 100%，是否返回正确错误
+        assert_noop!(
+            KittiesModule::set_marketplace_fee(Origin::root(), Permill::from_percent(91)),
+            Error::<Test>::InvalidFeeRate
+        );
+        assert_noop!(
+            KittiesModule::set_creator_royalty(Origin::root(), Permill::from_percent(96)),
+            Error::<Test>::InvalidFeeRate
+        );
+
+        assert_ok!(KittiesModule::set_marketplace_fee(Origin::root(), Permill::from_percent(20)));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::MarketplaceFeeSet(
+            Permill::from_percent(20),
+        )));
+        assert_ok!(KittiesModule::set_creator_royalty(Origin::root(), Permill::zero()));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::CreatorRoyaltySet(
+            Permill::zero(),
+        )));
+
+        //按新比例结算
+        create_kitty(ALICE, Gender::Male);
+        assert_ok!(KittiesModule::sale(Origin::signed(ALICE), 0, Some(5_000)));
+        assert_ok!(KittiesModule::buy(Origin::signed(BOB), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::KittySaleOut(
+            BOB,
+            0,
+            5_000,
+            1_000,
+            0,
+        )));
+        assert_eq!(Balances::free_balance(ALICE), 10_000 + 4_000);
+    });
+}
+
+#[test]
+fn can_max_kitties_per_account_work() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..MaxKittiesPerAccount::get() {
+            assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        }
+        set_gender(0, Gender::Female);
+        set_gender(1, Gender::Male);
+
+        //检查拥有数量达到上限时获得Kitty，是否返回正确错误
+        assert_noop!(
+            KittiesModule::create(Origin::signed(ALICE)),
+            Error::<Test>::TooManyKitties
+        );
+        assert_noop!(
+            KittiesModule::bread(Origin::signed(ALICE), 0, 1),
+            Error::<Test>::TooManyKitties
+        );
+
+        let kitty_id = create_kitty(BOB, Gender::Male);
+        assert_noop!(
+            KittiesModule::transfer(Origin::signed(BOB), ALICE, kitty_id),
+            Error::<Test>::TooManyKitties
+        );
+        assert_noop!(
+            KittiesModule::transfer_from(Origin::signed(BOB), BOB, ALICE, kitty_id),
+            Error::<Test>::TooManyKitties
+        );
+        assert_ok!(KittiesModule::sale(Origin::signed(BOB), kitty_id, Some(1_000)));
+        assert_noop!(
+            KittiesModule::buy(Origin::signed(ALICE), kitty_id),
+            Error::<Test>::TooManyKitties
+        );
+
+        //销毁后可再次获得
+        assert_ok!(KittiesModule::burn(Origin::signed(ALICE), 4, 0));
+        assert_ok!(KittiesModule::buy(Origin::signed(ALICE), kitty_id));
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), MaxKittiesPerAccount::get());
+    });
+}
+
+#[test]
+fn can_set_max_kitties_per_account_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(KittiesModule::max_kitties_per_account(), MaxKittiesPerAccount::get());
+        //检查非 ForceOrigin 调用，是否返回正确错误
+        assert_noop!(
+            KittiesModule::set_max_kitties_per_account(Origin::signed(ALICE), 1),
+            BadOrigin
+        );
+        //检查超过 T::MaxKittiesPerAccount，是否返回正确错误
+        assert_noop!(
+            KittiesModule::set_max_kitties_per_account(
+                Origin::root(),
+                MaxKittiesPerAccount::get() + 1
+            ),
+            Error::<Test>::MaxKittiesTooHigh
+        );
+
+        create_kitty(ALICE, Gender::Male);
+        create_kitty(ALICE, Gender::Female);
+        assert_ok!(KittiesModule::set_max_kitties_per_account(Origin::root(), 1));
+        System::assert_last_event(mock::Event::KittiesModule(
+            crate::Event::MaxKittiesPerAccountSet(1),
+        ));
+        assert_eq!(KittiesModule::max_kitties_per_account(), 1);
+
+        //已超出上限的账户保留现有Kitty，但不能再获得新的Kitty
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), 2);
+        assert_noop!(
+            KittiesModule::create(Origin::signed(ALICE)),
+            Error::<Test>::TooManyKitties
+        );
+
+        assert_ok!(KittiesModule::set_max_kitties_per_account(Origin::root(), 3));
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_eq!(KittiesModule::owned_kitties_count(ALICE), 3);
+    });
+}
+
+#[test]
+fn can_auction_refund_winner_with_too_many_kitties() {
+    new_test_ext().execute_with(|| {
+        create_kitty(BOB, Gender::Male);
+        assert_ok!(KittiesModule::create_auction(Origin::signed(BOB), 0, 1_000, 10));
+        assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 1_500));
+        for _ in 0..MaxKittiesPerAccount::get() {
+            assert_ok!(KittiesModule::create(Origin::signed(3)));
+        }
+
+        //最高出价者拥有数量已达上限，拍卖作废并退还出价
+        run_to_block(11);
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::AuctionExpired(
+            BOB, 0,
+        )));
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+        assert_eq!(
+            Balances::reserved_balance(3),
+            MaxKittiesPerAccount::get() as u128 * KITTY_RESERVE
+        );
+    });
+}
+
+#[test]
+fn can_set_metadata_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::set_metadata(
+            Origin::signed(ALICE),
+            0,
+            b"Tom".to_vec(),
+            b"ipfs://cat".to_vec(),
+        ));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::MetadataSet(
+            ALICE,
+            0,
+            b"Tom".to_vec(),
+            b"ipfs://cat".to_vec(),
+        )));
+        let metadata = KittiesModule::metadata(0).unwrap();
+        assert_eq!(metadata.depositor, ALICE);
+        assert_eq!(metadata.name, b"Tom".to_vec());
+        assert_eq!(metadata.uri, b"ipfs://cat".to_vec());
+        //按字节数质押
+        assert_eq!(metadata.deposit, 100 + 10 * 13);
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE + 230);
+
+        //重新设置时退还差额
+        assert_ok!(KittiesModule::set_metadata(Origin::signed(ALICE), 0, b"T".to_vec(), vec![]));
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE + 110);
+
+        assert_noop!(
+            KittiesModule::set_metadata(Origin::signed(BOB), 0, vec![], vec![]),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            KittiesModule::set_metadata(Origin::signed(ALICE), 0, vec![0u8; 33], vec![]),
+            Error::<Test>::BadMetadata
+        );
+        assert_noop!(
+            KittiesModule::set_metadata(Origin::signed(ALICE), 0, vec![], vec![0u8; 33]),
+            Error::<Test>::BadMetadata
+        );
+    });
+}
+
+#[test]
+fn can_clear_metadata_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::set_metadata(Origin::signed(ALICE), 0, b"Tom".to_vec(), vec![]));
+
+        assert_noop!(
+            KittiesModule::clear_metadata(Origin::signed(BOB), 0),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(KittiesModule::clear_metadata(Origin::signed(ALICE), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::MetadataCleared(
+            ALICE, 0,
+        )));
+        assert_eq!(KittiesModule::metadata(0), None);
+        assert_eq!(Balances::reserved_balance(ALICE), KITTY_RESERVE);
+        assert_noop!(
+            KittiesModule::clear_metadata(Origin::signed(ALICE), 0),
+            Error::<Test>::NoMetadata
+        );
+    });
+}
+
+#[test]
+fn can_metadata_deposit_refund_to_depositor() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::set_metadata(Origin::signed(ALICE), 0, b"Tom".to_vec(), vec![]));
+        assert_ok!(KittiesModule::transfer_from(Origin::signed(ALICE), ALICE, BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+        //转移后质押仍由原设置者承担
+        assert_eq!(Balances::reserved_balance(ALICE), 130);
+
+        //新拥有者重新设置，原设置者的质押被退还
+        assert_ok!(KittiesModule::set_metadata(Origin::signed(BOB), 0, b"Jerry".to_vec(), vec![]));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::reserved_balance(BOB), KITTY_RESERVE + 150);
+
+        //销毁时清除元数据并退还质押
+        assert_ok!(KittiesModule::burn(Origin::signed(BOB), 0, 0));
+        assert_eq!(KittiesModule::metadata(0), None);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), 20_000);
+    });
+}
+
+#[test]
+fn can_metadata_reclaim_after_transfer() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        assert_ok!(KittiesModule::set_metadata(Origin::signed(ALICE), 0, b"Tom".to_vec(), vec![]));
+        assert_ok!(KittiesModule::transfer(Origin::signed(ALICE), BOB, 0));
+        assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), 0));
+
+        //检查既非拥有者也非设置者清除，是否返回正确错误
+        assert_noop!(
+            KittiesModule::clear_metadata(Origin::signed(3), 0),
+            Error::<Test>::NotOwner
+        );
+
+        //原设置者清除元数据并取回质押
+        assert_ok!(KittiesModule::clear_metadata(Origin::signed(ALICE), 0));
+        System::assert_last_event(mock::Event::KittiesModule(crate::Event::MetadataCleared(
+            ALICE, 0,
+        )));
+        assert_eq!(KittiesModule::metadata(0), None);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 10_000);
+        assert_eq!(KittiesModule::owner(0), Some(BOB));
+    });
+}
+
+#[test]
+fn can_traits_of_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(ALICE)));
+        Kitties::<Test>::mutate(0, |kitty| {
+            if let Some(kitty) = kitty {
+                kitty.dna[kitty_genetics::BODY_BYTE] = 255;
+                kitty.dna[kitty_genetics::PATTERN_BYTE] = 0;
+                kitty.dna[kitty_genetics::EYE_COLOUR_BYTE] = 200;
+                kitty.dna[GENDER_BYTE] = 1;
+            }
+        });
+
+        let traits = KittiesModule::traits_of(0).unwrap();
+        assert_eq!(traits.body, kitty_genetics::Body::Cymric);
+        assert_eq!(traits.pattern, kitty_genetics::Pattern::Solid);
+        assert_eq!(traits.eye_colour, kitty_genetics::EyeColour::Blue);
+        assert_eq!(traits.gender, Gender::Female);
+        assert_eq!(traits.rarity, kitty_genetics::Rarity::Rare);
+        assert_eq!(KittiesModule::gender_of(0), Some(traits.gender));
+        assert_eq!(KittiesModule::traits_of(1), None);
+    });
+}
+
+// 创建一对Kitty并繁殖，返回父母及幼崽的DNA
+fn breed_pair() -> ([u8; 16], [u8; 16], [u8; 16]) {
+    create_kitty(ALICE, Gender::Female);
+    create_kitty(ALICE, Gender::Male);
+    assert_ok!(KittiesModule::bread(Origin::signed(ALICE), 0, 1));
+    (
+        KittiesModule::kitties(0).unwrap().dna,
+        KittiesModule::kitties(1).unwrap().dna,
+        KittiesModule::kitties(2).unwrap().dna,
+    )
+}
+
+#[test]
+fn can_bread_follow_genome() {
+    let first = new_test_ext().execute_with(|| {
+        run_to_block(3);
+        let seed = (
+            RandomnessCollectiveFlip::random_seed(),
+            &ALICE,
+            System::extrinsic_index(),
+        )
+            .using_encoded(sp_io::hashing::blake2_128);
+        let (matron, sire, child) = breed_pair();
+
+        //幼崽按基因组规则由父母DNA及随机种子决定
+        assert_eq!(child, kitty_genetics::breed(&matron, &sire, &seed));
+        child
+    });
+
+    //相同区块及交易顺序得到相同的幼崽
+    let second = new_test_ext().execute_with(|| {
+        run_to_block(3);
+        breed_pair().2
+    });
+    assert_eq!(first, second);
+}
@@ -12,7 +12,8 @@ use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::traits::{
-    AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify,
+    AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+    NumberFor, Verify,
 };
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
@@ -32,7 +33,7 @@ pub use frame_support::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
     },
-    StorageValue,
+    PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -62,6 +63,12 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// Index of a kitty in `pallet_kitties`.
+pub type KittyIndex = u32;
+
+/// Identifier of an asset in `pallet_assets`.
+pub type AssetId = u32;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -105,7 +112,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 100,
+    spec_version: 101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -243,11 +250,12 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
     pub const ExistentialDeposit: u128 = 500;
     pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
     type MaxLocks = MaxLocks;
-    type MaxReserves = ();
+    type MaxReserves = MaxReserves;
     type ReserveIdentifier = [u8; 8];
     /// The type for recording an account's balance.
     type Balance = Balance;
@@ -290,7 +298,33 @@ parameter_types! {
 	pub const StringLimit: u32 = 50;
 	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
 	pub const MetadataDepositPerByte: Balance = 1 * DOLLARS;
-	pub const KittyReserve: u64 = 1_000;
+	pub const KittyReserve: Balance = 1 * DOLLARS;
+	pub const KittyTransferExpiry: BlockNumber = 1 * DAYS;
+	pub const KittyBaseCooldown: BlockNumber = 1 * MINUTES;
+	pub const KittyMaxCooldown: BlockNumber = 7 * DAYS;
+	pub const KittyMaxAuctionDuration: BlockNumber = 30 * DAYS;
+	pub const KittyAuctionExtension: BlockNumber = 10 * MINUTES;
+	pub const KittyMaxAuctionsPerBlock: u32 = 50;
+	pub const KittyMaxOfferDuration: BlockNumber = 30 * DAYS;
+	pub const KittyMaxKittiesPerAccount: u32 = 100;
+	pub const KittyStringLimit: u32 = 128;
+	pub const KittyMetadataDepositBase: Balance = 1 * DOLLARS;
+	pub const KittyMetadataDepositPerByte: Balance = 10 * CENTS;
+	pub const KittyMarketplaceFee: Permill = Permill::from_percent(2);
+	pub const KittyCreatorRoyalty: Permill = Permill::from_percent(5);
+	pub const KittiesPalletId: PalletId = PalletId(*b"py/kitty");
+	pub KittyMarketplaceAccount: AccountId = KittiesPalletId::get().into_account();
+}
+
+// 基准测试中创建可供任意账户持有的资产
+#[cfg(feature = "runtime-benchmarks")]
+pub struct KittyAssetsBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_kitties::BenchmarkHelper<AssetId> for KittyAssetsBenchmarkHelper {
+    fn create_asset(asset_id: AssetId) {
+        Assets::force_create(Origin::root(), asset_id, AccountId::default().into(), true, 1)
+            .expect("asset does not exist yet; qed");
+    }
 }
 
 impl pallet_kitties::Config for Runtime {
@@ -299,14 +333,36 @@ impl pallet_kitties::Config for Runtime {
     type Currency = Balances;
     // type AssetDeposit = ApprovalDeposit;
     type KittyReserve = KittyReserve;
-    type KittyIndex = u32;
+    type KittyIndex = KittyIndex;
+    type TransferExpiry = KittyTransferExpiry;
+    type BaseCooldown = KittyBaseCooldown;
+    type MaxCooldown = KittyMaxCooldown;
+    type MaxAuctionDuration = KittyMaxAuctionDuration;
+    type AuctionExtension = KittyAuctionExtension;
+    type MaxAuctionsPerBlock = KittyMaxAuctionsPerBlock;
+    type MaxOfferDuration = KittyMaxOfferDuration;
+    type MaxKittiesPerAccount = KittyMaxKittiesPerAccount;
+    type StringLimit = KittyStringLimit;
+    type MetadataDepositBase = KittyMetadataDepositBase;
+    type MetadataDepositPerByte = KittyMetadataDepositPerByte;
+    type MarketplaceFee = KittyMarketplaceFee;
+    type CreatorRoyalty = KittyCreatorRoyalty;
+    // 与交易手续费一致，市场手续费直接销毁
+    type OnMarketplaceFee = ();
+    // 以资产成交时市场手续费转入本模块账户，不销毁资产
+    type MarketplaceAccount = KittyMarketplaceAccount;
+    type Assets = Assets;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = KittyAssetsBenchmarkHelper;
+    type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
     // type Balance = u64;
 }
 
 impl pallet_assets::Config for Runtime {
     type Event = Event;
     type Balance = u64;
-    type AssetId = u32;
+    type AssetId = AssetId;
     type Currency = Balances;
     type ForceOrigin = frame_system::EnsureRoot<AccountId>;
     type AssetDeposit = AssetDeposit;
@@ -338,7 +394,7 @@ construct_runtime!(
         Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
         // Include the custom logic from the pallet-template in the runtime.
         TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
-        Kitties: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+        Kitties: pallet_kitties::{Pallet, Call, Storage, Config<T>, Event<T>},
     }
 );
 
@@ -495,6 +551,45 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, AccountId, KittyIndex, Balance, BlockNumber, AssetId>
+        for Runtime
+    {
+        fn kitty(kitty_id: KittyIndex) -> Option<pallet_kitties::KittyInfoOf<Runtime>> {
+            Kitties::kitty_info(kitty_id)
+        }
+        fn kitties_of_owner(
+            owner: AccountId,
+            start_after: Option<KittyIndex>,
+            limit: u32,
+        ) -> Vec<KittyIndex> {
+            Kitties::kitties_of_owner(&owner, start_after, limit)
+        }
+        fn kitties_for_sale(
+            start_after: Option<KittyIndex>,
+            limit: u32,
+        ) -> Vec<(KittyIndex, Balance, Option<AssetId>)> {
+            Kitties::kitties_for_sale(start_after, limit)
+        }
+        fn kitties_count() -> KittyIndex {
+            Kitties::kitties_count()
+        }
+        fn breeding_ready_at(kitty_id: KittyIndex) -> Option<BlockNumber> {
+            Kitties::breeding_ready_at(kitty_id)
+        }
+        fn gender(kitty_id: KittyIndex) -> Option<pallet_kitties::Gender> {
+            Kitties::gender_of(kitty_id)
+        }
+        fn traits(kitty_id: KittyIndex) -> Option<pallet_kitties::Traits> {
+            Kitties::traits_of(kitty_id)
+        }
+        fn current_price(kitty_id: KittyIndex) -> Option<(Balance, Option<AssetId>)> {
+            Kitties::current_price(kitty_id)
+        }
+        fn metadata(kitty_id: KittyIndex) -> Option<pallet_kitties::KittyMetadata<AccountId, Balance>> {
+            Kitties::metadata(kitty_id)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(
@@ -525,6 +620,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
             add_benchmark!(params, batches, pallet_template, TemplateModule);
+            add_benchmark!(params, batches, pallet_kitties, Kitties);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)